@@ -94,10 +94,26 @@ def run_rust_bump(iterations):
     return polyglot_compute_lab.run_rust_bump_py(iterations)
 
 
+def run_rust_soa_bump(iterations):
+    return polyglot_compute_lab.run_rust_soa_bump_py(iterations)
+
+
 def run_rust_zipper(iterations):
     return polyglot_compute_lab.run_rust_zipper_py(iterations)
 
 
+def run_rust_gap_buffer(iterations):
+    return polyglot_compute_lab.run_rust_gap_buffer_py(iterations)
+
+
+def run_rust_unrolled(iterations):
+    return polyglot_compute_lab.run_rust_unrolled_py(iterations)
+
+
+def run_rust_arena_dll(iterations):
+    return polyglot_compute_lab.run_rust_arena_dll_py(iterations)
+
+
 def run_rust_wgpu(iterations):
     return polyglot_compute_lab.run_wgpu_py(iterations)
 
@@ -165,9 +181,22 @@ def main():
     # 5. Rust (Bump)
     results.append(benchmark("Rust (Bump)", run_rust_bump))
 
+    # 5b. Rust (Bump, SoA): 同じバンプ確保で、value/next/prev を別配列にしたもの
+    # Bumpとの差がメモリ配置の分、Zipperとの残りの差がアルゴリズムの分
+    results.append(benchmark("Rust (SoA-Bump)", run_rust_soa_bump))
+
     # 6. Rust (Zipper)
     results.append(benchmark("Rust (Zipper (Safe Rust))", run_rust_zipper))
 
+    # 6b. Rust (Gap Buffer)
+    results.append(benchmark("Rust (Gap Buffer)", run_rust_gap_buffer))
+
+    # 6c. Rust (Unrolled Linked List)
+    results.append(benchmark("Rust (Unrolled)", run_rust_unrolled))
+
+    # 6d. Rust (Index Arena DLL: unsafe なしの添字リンク)
+    results.append(benchmark("Rust (Arena DLL)", run_rust_arena_dll))
+
     # 7. WGPU (WebGPU)
     results.append(benchmark("WGPU (WebGPU)", run_rust_wgpu))
 
@@ -205,7 +234,7 @@ def main():
             prefix = "🚀 "
         elif "Unsafe" in name:
             prefix = "⚡ "
-        elif "Safe" in name:
+        elif "Safe" in name or "Arena" in name:
             prefix = "🛡️ "
         elif "deque" in name:
             prefix = "🐍 "
@@ -221,5 +250,41 @@ def main():
     print("-" * 65)
 
 
+# ==========================================
+# Parallel Scaling (python benchmark.py --parallel)
+# ==========================================
+def main_parallel():
+    # スレッド数を変えて、並列の一括構築+合計とリストランキングがコア数でどう伸びるかを見る
+    # （Rust側はGILを手放して rayon のプールで動く）
+    import os
+
+    N = 10_000_000
+    REPEAT = 5
+    cores = os.cpu_count() or 1
+    thread_counts = sorted({1, 2, 4, 8, 16, 32, 64, cores})
+    thread_counts = [t for t in thread_counts if t <= cores]
+    benches = [
+        ("Zipper (par sum)", polyglot_compute_lab.run_rust_zipper_par_sum_py),
+        ("Unsafe-Zipper (par sum)", polyglot_compute_lab.run_rust_unsafe_zipper_par_sum_py),
+        ("SoA-Bump (list rank)", polyglot_compute_lab.run_rust_soa_bump_par_rank_py),
+    ]
+
+    print(f"--- Parallel Scaling (N={N:,}, Repeat={REPEAT}, cores={cores}) ---")
+    print("-" * 65)
+    print(f"{'Implementation':<24} | {'Threads':>7} | {'Time (avg)':>10} | {'vs 1 thread':>11}")
+    print("-" * 65)
+    for name, func in benches:
+        base_ms = None
+        for threads in thread_counts:
+            total = timeit.timeit(lambda: func(N, threads), number=REPEAT)
+            avg_ms = total / REPEAT * 1000.0
+            base_ms = base_ms or avg_ms
+            print(f"{name:<24} | {threads:>7} | {avg_ms:>7.2f} ms | {base_ms / avg_ms:>10.2f}x")
+    print("-" * 65)
+
+
 if __name__ == "__main__":
-    main()
+    if len(sys.argv) > 1 and sys.argv[1] == "--parallel":
+        main_parallel()
+    else:
+        main()
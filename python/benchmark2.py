@@ -85,6 +85,26 @@ def run_rust_unsafe_zipper_insert(iterations):
     polyglot_compute_lab.run_rust_unsafe_zipper_insert_py(iterations)
 
 
+def run_rust_gap_buffer_insert(iterations):
+    polyglot_compute_lab.run_rust_gap_buffer_insert_py(iterations)
+
+
+def run_rust_unrolled_insert(iterations, block_size=256):
+    polyglot_compute_lab.run_rust_unrolled_insert_py(iterations, block_size)
+
+
+def run_rust_treap_insert(iterations):
+    polyglot_compute_lab.run_rust_treap_insert_py(iterations)
+
+
+def run_rust_skiplist_insert(iterations, seed=123456789):
+    polyglot_compute_lab.run_rust_skiplist_insert_py(iterations, seed)
+
+
+def run_rust_arena_dll_insert(iterations):
+    polyglot_compute_lab.run_rust_arena_dll_insert_py(iterations)
+
+
 # ==========================================
 # Zig Implementations (Random logic is inside Zig)
 # ==========================================
@@ -152,6 +172,27 @@ def main():
     # 7. Rust Unsafe Zipper (Vec Stack with Unsafe)
     results.append(benchmark("Rust (Unsafe-Zipper)", run_rust_unsafe_zipper_insert))
 
+    # 7b. Rust Gap Buffer (1本のバッファ + memmove)
+    results.append(benchmark("Rust (Gap Buffer)", run_rust_gap_buffer_insert))
+
+    # 7c. Rust Unrolled Linked List (ブロックサイズ別)
+    for block_size in (16, 256, 4096):
+        results.append(
+            benchmark(
+                f"Rust (Unrolled-{block_size})",
+                lambda n, b=block_size: run_rust_unrolled_insert(n, b),
+            )
+        )
+
+    # 7d. Rust Implicit Treap (O(log N) insert)
+    results.append(benchmark("Rust (Treap)", run_rust_treap_insert))
+
+    # 7e. Rust Indexable Skip List (期待 O(log N) insert、シード固定で再現可能)
+    results.append(benchmark("Rust (Skip List)", run_rust_skiplist_insert))
+
+    # 7f. Rust Index Arena DLL (UnsafeDll と同じ探索を unsafe なしで)
+    results.append(benchmark("Rust (Arena DLL)", run_rust_arena_dll_insert))
+
     # 8. Zig Zipper (ArrayList Stack)
     if zig_zipper_binary:
         results.append(benchmark("Zig (Zipper-WASM)", run_zig_zipper_insert))
@@ -175,5 +216,69 @@ def main():
     print("-" * 60)
 
 
+# ==========================================
+# Trace Replay (python benchmark2.py <trace-file>)
+# ==========================================
+def main_trace(path):
+    # 実アプリで記録したトレース（バイナリ/テキスト）を全バックエンドで再生する
+    with open(path, "rb") as f:
+        trace = f.read()
+    REPEAT = 5
+    backends = ["safe", "unsafe", "bump", "zipper", "unsafe-zipper", "gap-buffer", "unrolled", "treap", "skiplist", "arena-dll", "soa-bump"]
+
+    print(f"--- Trace Replay Benchmark ({path}, Repeat={REPEAT}) ---")
+    print("-" * 60)
+    print(f"{'Backend':<20} | {'Time (avg)':<10} | {'Checksum':<10}")
+    print("-" * 60)
+    for name in backends:
+        checksum = polyglot_compute_lab.replay_trace_py(name, trace)
+        total_time = timeit.timeit(
+            lambda: polyglot_compute_lab.replay_trace_py(name, trace), number=REPEAT
+        )
+        avg_ms = (total_time / REPEAT) * 1000.0
+        print(f"{name:<20} | {avg_ms:>8.2f} ms | {checksum}")
+    print("-" * 60)
+
+
+# ==========================================
+# Scaling (python benchmark2.py --scale)
+# ==========================================
+def main_scale():
+    # Nを数百万まで伸ばして、O(log N) の treap / skip list が O(N) シフトの実装を追い越す点を見る
+    # 1回が TIME_LIMIT_S を超えた実装は、それより大きいNでは測らない
+    SIZES = [10_000, 30_000, 100_000, 300_000, 1_000_000, 3_000_000]
+    TIME_LIMIT_S = 5.0
+    backends = [
+        ("Rust (Safe-Zipper)", run_rust_zipper_insert),
+        ("Rust (Unsafe-Zipper)", run_rust_unsafe_zipper_insert),
+        ("Rust (Gap Buffer)", run_rust_gap_buffer_insert),
+        ("Rust (Unrolled-256)", run_rust_unrolled_insert),
+        ("Rust (Treap)", run_rust_treap_insert),
+        ("Rust (Skip List)", run_rust_skiplist_insert),
+    ]
+
+    print("--- Random Insertion Scaling (Repeat=1) ---")
+    print("-" * (22 + 12 * len(SIZES)))
+    print(f"{'Implementation':<20} | " + "".join(f"{n:>12,}" for n in SIZES))
+    print("-" * (22 + 12 * len(SIZES)))
+    for name, func in backends:
+        cells = []
+        skipped = False
+        for n in SIZES:
+            if skipped:
+                cells.append(f"{'-':>12}")
+                continue
+            seconds = timeit.timeit(lambda: func(n), number=1)
+            cells.append(f"{seconds * 1000.0:>9.1f} ms")
+            skipped = seconds > TIME_LIMIT_S
+        print(f"{name:<20} | " + "".join(cells))
+    print("-" * (22 + 12 * len(SIZES)))
+
+
 if __name__ == "__main__":
-    main()
+    if len(sys.argv) > 1 and sys.argv[1] == "--scale":
+        main_scale()
+    elif len(sys.argv) > 1:
+        main_trace(sys.argv[1])
+    else:
+        main()
@@ -0,0 +1,311 @@
+use std::alloc::{self, Layout};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::ptr::NonNull;
+
+// ========================================================
+// Bump Arena
+// Zigの FixedBufferAllocator 相当。以前の `static mut HEAP` を所有型にしたもの
+// ========================================================
+
+/// アリーナの容量不足（Pythonでは `MemoryError`、JSでは例外になる）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError {
+    /// 要求されたバイト数
+    pub requested: usize,
+    /// アリーナの総容量（バイト）
+    pub capacity: usize,
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bump arena exhausted: {} more byte(s) requested, capacity is {} bytes (use a growable arena for larger N)",
+            self.requested, self.capacity
+        )
+    }
+}
+
+impl std::error::Error for AllocError {}
+
+// 1つの連続したバッファ
+struct Chunk {
+    base: NonNull<u8>,
+    capacity: usize,
+}
+
+impl Chunk {
+    fn new(capacity: usize) -> Self {
+        let layout = Self::layout(capacity);
+        // 未初期化のまま確保する（OSは触ったページしか実メモリを割り当てない）
+        let base = NonNull::new(unsafe { alloc::alloc(layout) }).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        Self { base, capacity }
+    }
+
+    fn layout(capacity: usize) -> Layout {
+        Layout::from_size_align(capacity, BumpArena::ALIGN).expect("arena capacity overflow")
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        unsafe { alloc::dealloc(self.base.as_ptr(), Self::layout(self.capacity)) };
+    }
+}
+
+/// バンプアロケータ。
+///
+/// 確保は `&self` で行える（オフセットは `Cell`）ので、1つのアリーナを複数の
+/// リストで共有できる。確保したメモリはアリーナの借用に結び付いているため、
+/// `reset()`（`&mut self`）やDropはそれを使うリストが全て消えるまで呼べない。
+/// `Cell` を持つので `Sync` ではなく、別スレッドから同時に確保されることはない。
+///
+/// 既定は固定容量で、溢れたら `AllocError` を返す。`BumpArena::growable` で作ると
+/// 溢れたときに倍の大きさのチャンクを追加して確保を続ける（既存のノードは動かない）。
+pub struct BumpArena {
+    // 確保済みのチャンク（最後が現在のチャンク）
+    chunks: RefCell<Vec<Chunk>>,
+    // 現在のチャンクの先頭・容量・使用量（毎回 RefCell を借りないためのキャッシュ）
+    base: Cell<NonNull<u8>>,
+    chunk_capacity: Cell<usize>,
+    offset: Cell<usize>,
+    // 現在より前のチャンクの容量・使用量の合計
+    retired_capacity: Cell<usize>,
+    retired_used: Cell<usize>,
+    growable: bool,
+}
+
+// チャンクはこのアリーナだけが所有しているので、丸ごと別スレッドへ移すのは安全
+unsafe impl Send for BumpArena {}
+
+impl BumpArena {
+    /// Zig版と同じ 64 MiB
+    pub const DEFAULT_CAPACITY: usize = 64 * 1024 * 1024;
+
+    /// 伸長モードの最初のチャンク
+    pub const DEFAULT_CHUNK: usize = 1024 * 1024;
+
+    // ノード（ポインタを含む）を置けるよう16バイト境界に揃える
+    const ALIGN: usize = 16;
+
+    /// `capacity` バイトの固定容量で確保する
+    pub fn new(capacity: usize) -> Self {
+        Self::with_first_chunk(capacity, false)
+    }
+
+    /// `first_chunk` バイトから始め、足りなくなるとチャンクを継ぎ足す
+    pub fn growable(first_chunk: usize) -> Self {
+        Self::with_first_chunk(first_chunk, true)
+    }
+
+    fn with_first_chunk(capacity: usize, growable: bool) -> Self {
+        let chunk = Chunk::new(capacity.max(1));
+        Self {
+            base: Cell::new(chunk.base),
+            chunk_capacity: Cell::new(chunk.capacity),
+            offset: Cell::new(0),
+            retired_capacity: Cell::new(0),
+            retired_used: Cell::new(0),
+            chunks: RefCell::new(vec![chunk]),
+            growable,
+        }
+    }
+
+    /// `T` を `count` 個置ける固定容量で確保する
+    pub fn for_items<T>(count: usize) -> Self {
+        Self::new(Self::bytes_for::<T>(count))
+    }
+
+    /// 最初のチャンクに `T` を `count` 個置ける伸長モードで確保する
+    pub fn growable_for_items<T>(count: usize) -> Self {
+        Self::growable(Self::bytes_for::<T>(count))
+    }
+
+    fn bytes_for<T>(count: usize) -> usize {
+        count.max(1).saturating_mul(std::mem::size_of::<T>().max(1))
+    }
+
+    pub fn is_growable(&self) -> bool {
+        self.growable
+    }
+
+    /// 全チャンクの容量の合計（バイト）
+    pub fn capacity(&self) -> usize {
+        self.retired_capacity.get() + self.chunk_capacity.get()
+    }
+
+    /// 使用済みバイト数（アラインメントの詰め物を含む）
+    pub fn used(&self) -> usize {
+        self.retired_used.get() + self.offset.get()
+    }
+
+    pub fn chunks(&self) -> usize {
+        self.chunks.borrow().len()
+    }
+
+    /// 全ての確保をまとめて捨てる（Zigの `fba.reset()`）。
+    /// `&mut self` なので、このアリーナから確保した参照やリストが残っている間は呼べない。
+    /// 伸長モードで増えたチャンクは、最も大きい（最後の）ものだけを残して解放する。
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        if chunks.len() > 1 {
+            let last = chunks.pop().expect("arena has at least one chunk");
+            chunks.clear();
+            chunks.push(last);
+        }
+        self.retired_capacity.set(0);
+        self.retired_used.set(0);
+        self.offset.set(0);
+    }
+
+    /// `value` を置いて参照を返す。
+    /// アリーナは個別解放しないので、Dropを持つ型は置けない (`T: Copy`)。
+    // 確保ごとに別の領域を返すので、&self から &mut を返しても別名にならない (bumpalo と同じ)
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T: Copy>(&self, value: T) -> Result<&mut T, AllocError> {
+        let ptr = self.alloc_raw(Layout::new::<T>())?.cast::<T>();
+        unsafe {
+            ptr.as_ptr().write(value);
+            Ok(&mut *ptr.as_ptr())
+        }
+    }
+
+    /// 未初期化の領域を確保する
+    pub(crate) fn alloc_raw(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        assert!(layout.align() <= Self::ALIGN, "BumpArena supports alignment up to {}", Self::ALIGN);
+        if let Some(ptr) = self.bump(layout) {
+            return Ok(ptr);
+        }
+        if !self.growable {
+            return Err(AllocError { requested: layout.size(), capacity: self.capacity() });
+        }
+        self.grow(layout.size());
+        Ok(self.bump(layout).expect("fresh chunk fits the request"))
+    }
+
+    // 現在のチャンクから切り出す（足りなければ None）
+    fn bump(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let start = self.offset.get().checked_add(layout.align() - 1)? & !(layout.align() - 1);
+        let end = start.checked_add(layout.size())?;
+        if end > self.chunk_capacity.get() {
+            return None;
+        }
+        self.offset.set(end);
+        // end <= capacity なのでチャンク内（サイズ0なら末尾の1つ先）
+        Some(unsafe { NonNull::new_unchecked(self.base.get().as_ptr().add(start)) })
+    }
+
+    // 倍の大きさ（最低でも要求サイズ）のチャンクを追加して現在のチャンクにする
+    fn grow(&self, request: usize) {
+        let size = self.chunk_capacity.get().saturating_mul(2).max(request + Self::ALIGN);
+        let chunk = Chunk::new(size);
+        self.retired_capacity.set(self.retired_capacity.get() + self.chunk_capacity.get());
+        self.retired_used.set(self.retired_used.get() + self.offset.get());
+        self.base.set(chunk.base);
+        self.chunk_capacity.set(chunk.capacity);
+        self.offset.set(0);
+        self.chunks.borrow_mut().push(chunk);
+    }
+}
+
+impl Default for BumpArena {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl fmt::Debug for BumpArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BumpArena")
+            .field("capacity", &self.capacity())
+            .field("used", &self.used())
+            .field("chunks", &self.chunks())
+            .field("growable", &self.growable)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_arena_reports_the_request_and_capacity_when_exhausted() {
+        let arena = BumpArena::new(32);
+        for i in 0..4u64 {
+            assert_eq!(*arena.alloc(i).unwrap(), i);
+        }
+        assert_eq!(arena.used(), 32);
+        assert_eq!(arena.alloc(4u64), Err(AllocError { requested: 8, capacity: 32 }));
+        assert_eq!(
+            arena.alloc_raw(Layout::array::<u64>(16).unwrap()).unwrap_err(),
+            AllocError { requested: 128, capacity: 32 }
+        );
+        // 失敗しても増えない
+        assert_eq!((arena.chunks(), arena.capacity(), arena.used()), (1, 32, 32));
+        assert!(arena.alloc(()).is_ok());
+    }
+
+    #[test]
+    fn allocations_are_aligned_and_padding_counts_as_used() {
+        let arena = BumpArena::new(64);
+        arena.alloc(1u8).unwrap();
+        let wide = arena.alloc(2u64).unwrap() as *mut u64;
+        assert_eq!(wide as usize % std::mem::align_of::<u64>(), 0);
+        assert_eq!(arena.used(), 16);
+    }
+
+    #[test]
+    fn growable_arena_doubles_its_chunks_and_keeps_old_values() {
+        let arena = BumpArena::growable(64);
+        let first: Vec<&mut u64> = (0..8u64).map(|i| arena.alloc(i).unwrap()).collect();
+        assert_eq!((arena.chunks(), arena.capacity()), (1, 64));
+
+        // 溢れたら倍 (128) のチャンクを足す
+        arena.alloc(8u64).unwrap();
+        assert_eq!((arena.chunks(), arena.capacity(), arena.used()), (2, 64 + 128, 72));
+        for i in 9..24u64 {
+            arena.alloc(i).unwrap();
+        }
+        assert_eq!(arena.chunks(), 2);
+        arena.alloc(24u64).unwrap();
+        assert_eq!((arena.chunks(), arena.capacity()), (3, 64 + 128 + 256));
+
+        // 倍でも足りない要求は、要求サイズ + アラインメント分のチャンクになる
+        arena.alloc_raw(Layout::from_size_align(4096, 8).unwrap()).unwrap();
+        assert_eq!((arena.chunks(), arena.capacity()), (4, 64 + 128 + 256 + 4096 + BumpArena::ALIGN));
+
+        // 既存のチャンクは動かない
+        assert!(first.iter().enumerate().all(|(i, v)| **v == i as u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "alignment up to 16")]
+    fn over_aligned_layouts_are_rejected() {
+        let arena = BumpArena::new(256);
+        let _ = arena.alloc_raw(Layout::from_size_align(32, 32).unwrap());
+    }
+
+    #[test]
+    fn reset_keeps_only_the_last_chunk() {
+        let mut arena = BumpArena::growable(64);
+        for i in 0..25u64 {
+            arena.alloc(i).unwrap();
+        }
+        assert_eq!(arena.chunks(), 3);
+
+        arena.reset();
+        assert_eq!((arena.chunks(), arena.capacity(), arena.used()), (1, 256, 0));
+        // 残したチャンクを頭から使い直す
+        for i in 0..32u64 {
+            arena.alloc(i).unwrap();
+        }
+        assert_eq!((arena.chunks(), arena.used()), (1, 256));
+
+        let mut fixed = BumpArena::new(32);
+        fixed.alloc(1u64).unwrap();
+        fixed.reset();
+        assert_eq!((fixed.chunks(), fixed.capacity(), fixed.used()), (1, 32, 0));
+    }
+}
@@ -0,0 +1,472 @@
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Index Arena) Implementation
+// Impl: Nodes in one Vec, linked by u32 index (no unsafe, no Rc/RefCell)
+// ========================================================
+
+// shader.wgsl の `heap[node_idx]` と同じく、ノードを1本の Vec に並べて添字で繋ぐ。
+// ポインタを持たないので unsafe も Rc<RefCell> も要らない。
+// スロットごとに世代番号を持ち、解放のたびに1つ進める。ハンドルは (添字, 世代) の組なので、
+// 解放済み（または別の値で再利用された）スロットを指す古いハンドルは `None` で弾かれる。
+
+// 添字の「無し」（WGSL版の 0u の代わり。0番も使うので最大値を番兵にする）
+const NIL: u32 = u32::MAX;
+
+/// ノードを指す安定したハンドル（挿入・削除で他のノードが動いても変わらない）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    index: u32,
+    generation: u32,
+}
+
+struct Node<T> {
+    value: T,
+    prev: u32,
+    next: u32,
+}
+
+enum Entry<T> {
+    Occupied(Node<T>),
+    // 空きスロットは単方向のフリーリストで繋ぐ
+    Free { next_free: u32 },
+}
+
+struct Slot<T> {
+    generation: u32, // 解放するたびに進める（u32 を一周すると古いハンドルと区別できなくなる）
+    entry: Entry<T>,
+}
+
+pub struct ArenaDll<T = i32> {
+    slots: Vec<Slot<T>>,
+    head: u32,
+    tail: u32,
+    len: usize,
+    free: u32,
+    // 最後に位置で触ったノードとその位置（近くへの連続した挿入・削除を速くする）
+    hint: Option<(u32, usize)>,
+}
+
+pub struct ArenaDllIter<'a, T = i32> {
+    list: &'a ArenaDll<T>,
+    current: u32,
+}
+
+impl<T: Clone> Iterator for ArenaDllIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.current == NIL {
+            return None;
+        }
+        let node = self.list.node(self.current);
+        self.current = node.next;
+        Some(node.value.clone())
+    }
+}
+
+impl<T> ArenaDll<T> {
+    /// 要素を持たない空のリスト
+    pub const fn new_empty() -> Self {
+        Self {
+            slots: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            len: 0,
+            free: NIL,
+            hint: None,
+        }
+    }
+
+    fn node(&self, index: u32) -> &Node<T> {
+        match &self.slots[index as usize].entry {
+            Entry::Occupied(node) => node,
+            Entry::Free { .. } => unreachable!("link to a free slot {}", index),
+        }
+    }
+
+    fn node_mut(&mut self, index: u32) -> &mut Node<T> {
+        match &mut self.slots[index as usize].entry {
+            Entry::Occupied(node) => node,
+            Entry::Free { .. } => unreachable!("link to a free slot {}", index),
+        }
+    }
+
+    // ハンドルが今も生きていれば、その添字
+    fn resolve(&self, handle: NodeHandle) -> Option<u32> {
+        let slot = self.slots.get(handle.index as usize)?;
+        match slot.entry {
+            Entry::Occupied(_) if slot.generation == handle.generation => Some(handle.index),
+            _ => None,
+        }
+    }
+
+    fn handle(&self, index: u32) -> NodeHandle {
+        NodeHandle { index, generation: self.slots[index as usize].generation }
+    }
+
+    /// ハンドルの指すノードがまだリストにあるか
+    pub fn contains(&self, handle: NodeHandle) -> bool {
+        self.resolve(handle).is_some()
+    }
+
+    /// ハンドルの指す値（解放済みなら `None`）
+    pub fn get_by_handle(&self, handle: NodeHandle) -> Option<&T> {
+        let index = self.resolve(handle)?;
+        Some(&self.node(index).value)
+    }
+
+    pub fn get_by_handle_mut(&mut self, handle: NodeHandle) -> Option<&mut T> {
+        let index = self.resolve(handle)?;
+        Some(&mut self.node_mut(index).value)
+    }
+
+    /// `index` 番目のノードのハンドル（範囲外なら `None`）
+    pub fn handle_at(&self, index: usize) -> Option<NodeHandle> {
+        let node = self.node_at(index);
+        (node != NIL).then(|| self.handle(node))
+    }
+
+    /// 末尾に追加して、そのハンドルを返す
+    pub fn push_back(&mut self, value: T) -> NodeHandle {
+        let tail = self.tail;
+        let node = self.link_between(tail, NIL, value);
+        self.handle(node)
+    }
+
+    /// 先頭に追加して、そのハンドルを返す
+    pub fn push_front(&mut self, value: T) -> NodeHandle {
+        let head = self.head;
+        let node = self.link_between(NIL, head, value);
+        self.hint = None;
+        self.handle(node)
+    }
+
+    /// `handle` の手前に挿入する（O(1)）。`handle` が古ければ何もせず `None`
+    pub fn insert_before(&mut self, handle: NodeHandle, value: T) -> Option<NodeHandle> {
+        let next = self.resolve(handle)?;
+        let prev = self.node(next).prev;
+        let node = self.link_between(prev, next, value);
+        self.hint = None;
+        Some(self.handle(node))
+    }
+
+    /// `handle` の後ろに挿入する（O(1)）。`handle` が古ければ何もせず `None`
+    pub fn insert_after(&mut self, handle: NodeHandle, value: T) -> Option<NodeHandle> {
+        let prev = self.resolve(handle)?;
+        let next = self.node(prev).next;
+        let node = self.link_between(prev, next, value);
+        self.hint = None;
+        Some(self.handle(node))
+    }
+
+    /// ハンドルの指すノードを取り除いて値を返す（O(1)）。古いハンドルなら `None`
+    pub fn remove_by_handle(&mut self, handle: NodeHandle) -> Option<T> {
+        let index = self.resolve(handle)?;
+        // 位置が分からなくなるので覚えていた位置は捨てる
+        self.hint = None;
+        Some(self.unlink(index))
+    }
+
+    // 空きスロットを再利用するか、Vec の末尾に足す
+    fn alloc(&mut self, node: Node<T>) -> u32 {
+        if self.free != NIL {
+            let index = self.free;
+            let slot = &mut self.slots[index as usize];
+            if let Entry::Free { next_free } = slot.entry {
+                self.free = next_free;
+            }
+            slot.entry = Entry::Occupied(node);
+            index
+        } else {
+            let index = u32::try_from(self.slots.len())
+                .ok()
+                .filter(|&i| i != NIL)
+                .expect("ArenaDll is full (u32 indices)");
+            self.slots.push(Slot { generation: 0, entry: Entry::Occupied(node) });
+            index
+        }
+    }
+
+    // prev と next の間に新しいノードを繋ぐ（どちらかが NIL なら端）
+    fn link_between(&mut self, prev: u32, next: u32, value: T) -> u32 {
+        let node = self.alloc(Node { value, prev, next });
+        if prev != NIL {
+            self.node_mut(prev).next = node;
+        } else {
+            self.head = node;
+        }
+        if next != NIL {
+            self.node_mut(next).prev = node;
+        } else {
+            self.tail = node;
+        }
+        self.len += 1;
+        node
+    }
+
+    // ノードを切り離し、スロットの世代を進めてフリーリストに戻す
+    fn unlink(&mut self, index: u32) -> T {
+        let slot = &mut self.slots[index as usize];
+        let entry = std::mem::replace(&mut slot.entry, Entry::Free { next_free: self.free });
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free = index;
+        let Entry::Occupied(node) = entry else {
+            unreachable!("unlink of a free slot {}", index);
+        };
+        if node.prev != NIL {
+            self.node_mut(node.prev).next = node.next;
+        } else {
+            self.head = node.next;
+        }
+        if node.next != NIL {
+            self.node_mut(node.next).prev = node.prev;
+        } else {
+            self.tail = node.prev;
+        }
+        self.len -= 1;
+        node.value
+    }
+
+    // index番目のノードを、head・tail・前回の位置のうち一番近いところから探す
+    // (範囲外なら NIL)
+    fn node_at(&self, index: usize) -> u32 {
+        if index >= self.len {
+            return NIL;
+        }
+        let mut start = (self.head, 0);
+        if self.len - 1 - index < index {
+            start = (self.tail, self.len - 1);
+        }
+        if let Some(hint) = self.hint {
+            if hint.1.abs_diff(index) < start.1.abs_diff(index) {
+                start = hint;
+            }
+        }
+        let (mut current, mut i) = start;
+        while i < index {
+            current = self.node(current).next;
+            i += 1;
+        }
+        while i > index {
+            current = self.node(current).prev;
+            i -= 1;
+        }
+        current
+    }
+}
+
+impl<T: Clone> SequenceBackend<T> for ArenaDll<T> {
+    type Iter<'a> = ArenaDllIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::new_empty()
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut list = Self::new_empty();
+        list.slots.reserve(capacity);
+        list
+    }
+
+    fn append(&mut self, value: T) {
+        self.push_back(value);
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        // Vec の伸長を1回にまとめる（空きスロットがあればそちらが先に使われる）
+        self.slots.reserve(values.len());
+        for value in values {
+            self.push_back(value.clone());
+        }
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        if index >= self.len {
+            self.push_back(value);
+            return;
+        }
+        let next = self.node_at(index);
+        let prev = self.node(next).prev;
+        let node = self.link_between(prev, next, value);
+        self.hint = Some((node, index));
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let node = self.node_at(index);
+        if node == NIL {
+            return None;
+        }
+        // 次のノードが同じ位置に詰まってくる
+        let next = self.node(node).next;
+        self.hint = (next != NIL).then_some((next, index));
+        Some(self.unlink(node))
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        let node = self.node_at(index);
+        (node != NIL).then(|| self.node(node).value.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> ArenaDllIter<'_, T> {
+        ArenaDllIter { list: self, current: self.head }
+    }
+
+    fn clear(&mut self) {
+        // Vec を捨てると同じ添字・同じ世代が再び使われて古いハンドルが通ってしまうので、
+        // 世代を進めて全スロットを空きにする
+        let mut free = NIL;
+        for (index, slot) in self.slots.iter_mut().enumerate().rev() {
+            if let Entry::Occupied(_) = slot.entry {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+            slot.entry = Entry::Free { next_free: free };
+            free = index as u32;
+        }
+        self.free = free;
+        self.head = NIL;
+        self.tail = NIL;
+        self.len = 0;
+        self.hint = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::Oracle;
+    use crate::workload::{OpMix, PositionDist, Workload};
+
+    // 前後のリンクとフリーリストの数を確かめてから、モデルと比べる
+    fn check(list: &ArenaDll, model: &[i32]) {
+        let mut prev = NIL;
+        let mut current = list.head;
+        let mut count = 0;
+        while current != NIL {
+            assert_eq!(list.node(current).prev, prev);
+            prev = current;
+            current = list.node(current).next;
+            count += 1;
+        }
+        assert_eq!(list.tail, prev);
+        assert_eq!(count, list.len);
+
+        let mut free = 0;
+        let mut next = list.free;
+        while next != NIL {
+            let Entry::Free { next_free } = list.slots[next as usize].entry else {
+                panic!("free list reaches an occupied slot {}", next);
+            };
+            next = next_free;
+            free += 1;
+        }
+        assert_eq!(count + free, list.slots.len());
+
+        assert_eq!(list.len(), model.len());
+        assert!(list.iter().eq(model.iter().copied()));
+        for (i, v) in model.iter().enumerate() {
+            assert_eq!(list.get(i), Some(*v));
+        }
+        assert_eq!(list.get(model.len()), None);
+    }
+
+    // Sequential は前回の位置の近く（hint から辿る）、Uniform は遠くへ飛ぶ
+    #[test]
+    fn random_ops_match_the_oracle_with_the_position_hint() {
+        let mix = OpMix { append: 1, insert: 4, remove: 3, get: 1, sum: 1 };
+        for dist in [PositionDist::Sequential(1), PositionDist::Sequential(3), PositionDist::Uniform] {
+            let ops = Workload::new(2000, 0x5151_7a7a, mix, dist).with_prefill(10).generate();
+            let mut list = ArenaDll::new();
+            let mut oracle = Oracle::default();
+            for (step, op) in ops.into_iter().enumerate() {
+                assert_eq!(op.apply(&mut list), op.apply(&mut oracle), "{:?} at step {}", op, step);
+                check(&list, &oracle.0);
+            }
+        }
+    }
+
+    #[test]
+    fn handle_operations_match_positions() {
+        let mut list = ArenaDll::new();
+        let b = list.push_back(2);
+        let a = list.push_front(1);
+        let d = list.push_back(4);
+        let c = list.insert_before(d, 3).unwrap();
+        let e = list.insert_after(d, 5).unwrap();
+        check(&list, &[1, 2, 3, 4, 5]);
+        assert_eq!(list.handle_at(2), Some(c));
+        assert_eq!(list.handle_at(5), None);
+
+        *list.get_by_handle_mut(b).unwrap() = 20;
+        assert_eq!(list.remove_by_handle(a), Some(1));
+        assert_eq!(list.remove_by_handle(e), Some(5));
+        check(&list, &[20, 3, 4]);
+        assert_eq!(list.get_by_handle(c), Some(&3));
+    }
+
+    #[test]
+    fn stale_handles_are_rejected_after_remove_and_reuse() {
+        let mut list = ArenaDll::new();
+        let a = list.push_back(1);
+        let b = list.push_back(2);
+        assert_eq!(list.remove_by_handle(a), Some(1));
+        assert_eq!(list.remove_by_handle(a), None);
+
+        // 同じスロットが別の値で再利用されても、古いハンドルは通らない
+        let c = list.push_back(3);
+        assert_eq!(c.index, a.index);
+        assert!(!list.contains(a));
+        assert_eq!(list.get_by_handle(a), None);
+        assert_eq!(list.insert_before(a, 9), None);
+        assert_eq!(list.insert_after(a, 9), None);
+        assert_eq!(list.get_by_handle(c), Some(&3));
+        check(&list, &[2, 3]);
+        assert!(list.contains(b));
+    }
+
+    #[test]
+    fn stale_handles_are_rejected_after_clear() {
+        let mut list = ArenaDll::new();
+        let handles: Vec<NodeHandle> = (0..5).map(|v| list.push_back(v)).collect();
+        list.remove_by_handle(handles[2]);
+        list.clear();
+        check(&list, &[]);
+        for &handle in &handles {
+            assert!(!list.contains(handle));
+            assert_eq!(list.get_by_handle(handle), None);
+            assert_eq!(list.get_by_handle_mut(handle), None);
+            assert_eq!(list.remove_by_handle(handle), None);
+            assert_eq!(list.insert_after(handle, 0), None);
+        }
+
+        // 空きスロットを先頭から使い直しても、世代が違うので古いハンドルは None のまま
+        let fresh: Vec<NodeHandle> = (10..15).map(|v| list.push_back(v)).collect();
+        assert_eq!(list.slots.len(), 5);
+        for (old, new) in handles.iter().zip(&fresh) {
+            assert_eq!(old.index, new.index);
+            assert_eq!(list.get_by_handle(*old), None);
+            assert!(list.contains(*new));
+        }
+        check(&list, &[10, 11, 12, 13, 14]);
+    }
+}
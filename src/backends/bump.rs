@@ -0,0 +1,429 @@
+use std::alloc::Layout;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use super::arena::{AllocError, BumpArena};
+use crate::SequenceBackend;
+
+// ========================================================
+// Rust (Bump Allocation) Implementation
+// Impl: Manual Bump Allocator (Zig style)
+// ========================================================
+
+// 以前はZigと同じ64MBの `static mut HEAP` を全リストで共有し、new() でオフセットを
+// 巻き戻していたため、2つ同時に使うと互いのノードを上書きしていた。
+// 今はリストごとに所有するか、呼び出し側が渡した `BumpArena` から確保する。
+
+// アリーナは個別にDropしないので、要素は `T: Copy` に限る
+#[derive(Clone, Copy)]
+struct BumpNode<T> {
+    value: T,
+    next: *mut BumpNode<T>,
+    prev: *mut BumpNode<T>,
+}
+
+// 自前のアリーナか、呼び出し側から借りたアリーナか
+enum ArenaSlot<'a> {
+    Owned(BumpArena),
+    Shared(&'a BumpArena),
+}
+
+impl Deref for ArenaSlot<'_> {
+    type Target = BumpArena;
+
+    fn deref(&self) -> &BumpArena {
+        match self {
+            ArenaSlot::Owned(arena) => arena,
+            ArenaSlot::Shared(arena) => arena,
+        }
+    }
+}
+
+/// バンプアロケータ上の双方向リスト。
+///
+/// `SequenceBackend::new()` / `with_capacity()` は専用のアリーナを持つ。
+/// `GROWABLE = false`（既定）なら固定容量で、溢れると `try_append` / `try_insert` が
+/// `AllocError` を返す（`append` / `insert` はpanic）。`GrowableBumpDll` はチャンクを継ぎ足す。
+/// 複数のリストで1つのアリーナを使う場合は `BumpDll::in_arena` を使う
+/// （ノードはアリーナの借用 `'a` より長生きできない）。
+pub struct BumpDll<'a, T = i32, const GROWABLE: bool = false> {
+    arena: ArenaSlot<'a>,
+    head: *mut BumpNode<T>,
+    tail: *mut BumpNode<T>,
+    len: usize,
+    // removeしたノードを next で繋いだ単方向のフリーリスト（再利用用）
+    free: *mut BumpNode<T>,
+}
+
+/// 容量が足りなくなるとアリーナにチャンクを追加する `BumpDll`（2.8M ノードを超えるN用）
+pub type GrowableBumpDll<'a, T = i32> = BumpDll<'a, T, true>;
+
+pub struct BumpDllIter<'a, T = i32> {
+    current: *mut BumpNode<T>,
+    _marker: PhantomData<&'a BumpNode<T>>,
+}
+
+impl<T: Copy> Iterator for BumpDllIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.current.is_null() {
+            return None;
+        }
+        unsafe {
+            let value = (*self.current).value;
+            self.current = (*self.current).next;
+            Some(value)
+        }
+    }
+}
+
+// アリーナを渡す場合は伸長するかどうかをアリーナ側が決めるので、既定の型だけに置く
+// （`BumpDll::in_arena(..)` で型パラメータを推論できるように）
+impl<'a, T: Copy> BumpDll<'a, T> {
+    /// 共有のアリーナから確保するリストを作る
+    pub fn in_arena(arena: &'a BumpArena) -> Self {
+        Self::with_slot(ArenaSlot::Shared(arena))
+    }
+
+    /// 専用のアリーナを渡して作る（`BumpArena::growable` を渡せば伸長する）
+    pub fn with_arena(arena: BumpArena) -> Self {
+        Self::with_slot(ArenaSlot::Owned(arena))
+    }
+}
+
+impl<'a, T: Copy, const GROWABLE: bool> BumpDll<'a, T, GROWABLE> {
+    fn with_slot(arena: ArenaSlot<'a>) -> Self {
+        Self {
+            arena,
+            head: std::ptr::null_mut(),
+            tail: std::ptr::null_mut(),
+            len: 0,
+            free: std::ptr::null_mut(),
+        }
+    }
+
+    pub fn arena(&self) -> &BumpArena {
+        &self.arena
+    }
+
+    // 独自の割り当て関数 (mallocの代わり)
+    fn alloc_node(&mut self, value: T) -> Result<*mut BumpNode<T>, AllocError> {
+        let node = BumpNode {
+            value,
+            next: std::ptr::null_mut(),
+            prev: std::ptr::null_mut(),
+        };
+        // フリーリストにノードがあればそれを再利用する（Bumpしない）
+        if !self.free.is_null() {
+            let ptr = self.free;
+            unsafe {
+                self.free = (*ptr).next;
+                *ptr = node;
+            }
+            return Ok(ptr);
+        }
+        // 以前は境界チェックがコメントアウトされていて、64MBを超えると範囲外に書き込んでいた
+        Ok(self.arena.alloc(node)?)
+    }
+
+    // index番目のノードを先頭から探す (O(N) traversal)
+    fn node_at(&self, index: usize) -> *mut BumpNode<T> {
+        if index >= self.len {
+            return std::ptr::null_mut();
+        }
+        unsafe {
+            let mut current = self.head;
+            for _ in 0..index {
+                current = (*current).next;
+            }
+            current
+        }
+    }
+
+    // nodeをリストから切り離してフリーリストへ戻し、値を返す
+    // (nodeはこのリストに属する非nullのポインタであること)
+    unsafe fn unlink(&mut self, node: *mut BumpNode<T>) -> T {
+        let prev = (*node).prev;
+        let next = (*node).next;
+        if !prev.is_null() {
+            (*prev).next = next;
+        } else {
+            self.head = next;
+        }
+        if !next.is_null() {
+            (*next).prev = prev;
+        } else {
+            self.tail = prev;
+        }
+        self.len -= 1;
+
+        // Bumpアロケータ自体は個別解放できないので、自前のフリーリストに繋ぐ
+        (*node).next = self.free;
+        (*node).prev = std::ptr::null_mut();
+        self.free = node;
+        (*node).value
+    }
+}
+
+impl<'a, T: Copy, const GROWABLE: bool> SequenceBackend<T> for BumpDll<'a, T, GROWABLE> {
+    type Iter<'b> = BumpDllIter<'b, T> where Self: 'b;
+
+    fn new() -> Self {
+        if GROWABLE {
+            Self::with_slot(ArenaSlot::Owned(BumpArena::growable(BumpArena::DEFAULT_CHUNK)))
+        } else {
+            Self::with_slot(ArenaSlot::Owned(BumpArena::default()))
+        }
+    }
+
+    /// `capacity` ノード分のアリーナを確保する
+    /// （固定容量なら超えた分は `AllocError`、伸長モードなら最初のチャンクの大きさ）
+    fn with_capacity(capacity: usize) -> Self {
+        if GROWABLE {
+            Self::with_slot(ArenaSlot::Owned(BumpArena::growable_for_items::<BumpNode<T>>(capacity)))
+        } else {
+            Self::with_slot(ArenaSlot::Owned(BumpArena::for_items::<BumpNode<T>>(capacity)))
+        }
+    }
+
+    fn append(&mut self, value: T) {
+        if let Err(e) = self.try_append(value) {
+            panic!("{}", e);
+        }
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        if let Err(e) = self.try_insert(index, value) {
+            panic!("{}", e);
+        }
+    }
+
+    fn try_append(&mut self, value: T) -> Result<(), AllocError> {
+        // Box::new ではなく、自作allocを使う
+        let new_node = self.alloc_node(value)?;
+        unsafe {
+            if !self.tail.is_null() {
+                (*self.tail).next = new_node;
+                (*new_node).prev = self.tail;
+                self.tail = new_node;
+            } else {
+                self.head = new_node;
+                self.tail = new_node;
+            }
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        if let Err(e) = self.try_extend_from_slice(values) {
+            panic!("{}", e);
+        }
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        // フリーリストに残っているノードを先に使い、残りは1回のバンプで配列として確保する。
+        // 確保を先に済ませるので、失敗したときはフリーリストも含めてリストは変わらない
+        let mut reusable = 0;
+        let mut free = self.free;
+        while !free.is_null() && reusable < values.len() {
+            free = unsafe { (*free).next };
+            reusable += 1;
+        }
+        let (reused, rest) = values.split_at(reusable);
+        let nodes = if rest.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            let layout = Layout::array::<BumpNode<T>>(rest.len()).expect("node array size overflow");
+            self.arena.alloc_raw(layout)?.cast::<BumpNode<T>>().as_ptr()
+        };
+        for &value in reused {
+            self.try_append(value).expect("free-list nodes need no allocation");
+        }
+        if rest.is_empty() {
+            return Ok(());
+        }
+        // 隣同士を繋いでから tail の後ろに付ける
+        unsafe {
+            for (i, &value) in rest.iter().enumerate() {
+                let next = if i + 1 < rest.len() { nodes.add(i + 1) } else { std::ptr::null_mut() };
+                let prev = if i > 0 { nodes.add(i - 1) } else { self.tail };
+                nodes.add(i).write(BumpNode { value, next, prev });
+            }
+            if !self.tail.is_null() {
+                (*self.tail).next = nodes;
+            } else {
+                self.head = nodes;
+            }
+            self.tail = nodes.add(rest.len() - 1);
+        }
+        self.len += rest.len();
+        Ok(())
+    }
+
+    fn try_insert(&mut self, index: usize, value: T) -> Result<(), AllocError> {
+        // インデックスが範囲外なら、alloc_nodeする前に末尾に追加する
+        // (以前は確保したnew_nodeを放置してappendしていたため、1ノード分リークしていた)
+        if index >= self.len {
+            return self.try_append(value);
+        }
+        // ここが重要: Box::new ではなく、Bumpアロケータからメモリをもらう
+        let new_node = self.alloc_node(value)?;
+        unsafe {
+            if index == 0 {
+                if !self.head.is_null() {
+                    (*self.head).prev = new_node;
+                    (*new_node).next = self.head;
+                } else {
+                    self.tail = new_node;
+                }
+                self.head = new_node;
+                self.len += 1;
+                return Ok(());
+            }
+
+            let mut current = self.head;
+            let mut i = 0;
+            
+            // 挿入位置までトラバーサル
+            while !current.is_null() {
+                if i == index {
+                    // currentの手前に挿入
+                    let prev = (*current).prev;
+                    (*current).prev = new_node;
+                    (*new_node).next = current;
+                    (*new_node).prev = prev;
+                    if !prev.is_null() {
+                        (*prev).next = new_node;
+                    }
+                    self.len += 1;
+                    return Ok(());
+                }
+                current = (*current).next;
+                i += 1;
+            }
+        }
+        // index < len なのでループ内で必ず挿入される
+        unreachable!("insert position {} not found in a list of length {}", index, self.len)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let node = self.node_at(index);
+        if node.is_null() {
+            return None;
+        }
+        unsafe { Some(self.unlink(node)) }
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        if self.head.is_null() {
+            return None;
+        }
+        unsafe { Some(self.unlink(self.head)) }
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        if self.tail.is_null() {
+            return None;
+        }
+        unsafe { Some(self.unlink(self.tail)) }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        let node = self.node_at(index);
+        if node.is_null() {
+            None
+        } else {
+            unsafe { Some((*node).value) }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> BumpDllIter<'_, T> {
+        BumpDllIter {
+            current: self.head,
+            _marker: PhantomData,
+        }
+    }
+
+    fn clear(&mut self) {
+        // 専用アリーナならオフセットを巻き戻すだけで全解放（Zigのfba.reset()と同じ）
+        if let ArenaSlot::Owned(arena) = &mut self.arena {
+            arena.reset();
+            self.free = std::ptr::null_mut();
+        } else if !self.tail.is_null() {
+            // 共有アリーナは他のリストも使っているので、チェーンごとフリーリストの先頭に繋ぐ (O(1))
+            unsafe { (*self.tail).next = self.free; }
+            self.free = self.head;
+        }
+        self.head = std::ptr::null_mut();
+        self.tail = std::ptr::null_mut();
+        self.len = 0;
+    }
+}
+
+// 一括構築を FromIterator / Extend からも使えるようにする（backends/mod.rs の impl_bulk_traits と同じ）
+impl<T: Copy, const GROWABLE: bool> FromIterator<T> for BumpDll<'_, T, GROWABLE> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        <Self as SequenceBackend<T>>::from_slice(&values)
+    }
+}
+
+impl<T: Copy, const GROWABLE: bool> Extend<T> for BumpDll<'_, T, GROWABLE> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let values: Vec<T> = iter.into_iter().collect();
+        self.extend_from_slice(&values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_extend_leaves_the_list_and_free_list_untouched() {
+        let mut list = BumpDll::<i32>::with_capacity(4);
+        list.try_extend_from_slice(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(list.remove(1), Some(2));
+
+        // 空きノード1つでは足りず、残りの確保で失敗する
+        assert_eq!(
+            list.try_extend_from_slice(&[5, 6]),
+            Err(AllocError { requested: std::mem::size_of::<BumpNode<i32>>(), capacity: list.arena.capacity() })
+        );
+        assert!(list.iter().eq([1, 3, 4]));
+        assert_eq!(list.len(), 3);
+
+        // 空きノードは残っているので1つなら入る
+        list.try_extend_from_slice(&[7]).unwrap();
+        assert!(list.iter().eq([1, 3, 4, 7]));
+        assert!(list.try_append(8).is_err());
+    }
+
+    #[test]
+    fn extend_reuses_free_nodes_before_bumping() {
+        let mut list = BumpDll::<i32>::with_capacity(6);
+        list.extend_from_slice(&[1, 2, 3]);
+        list.pop_front();
+        list.pop_back();
+        let used = list.arena.used();
+        list.try_extend_from_slice(&[4, 5, 6, 7]).unwrap();
+        assert!(list.iter().eq([2, 4, 5, 6, 7]));
+        // 空きノード2つを使い、残り2つ分だけアリーナが進む
+        assert_eq!(list.arena.used() - used, 2 * std::mem::size_of::<BumpNode<i32>>());
+        // 確保した配列の先頭は tail の後ろに、逆向きのリンクも繋がっている
+        let backwards: Vec<i32> = std::iter::successors(Some(list.tail), |&node| {
+            let prev = unsafe { (*node).prev };
+            (!prev.is_null()).then_some(prev)
+        })
+        .map(|node| unsafe { (*node).value })
+        .collect();
+        assert_eq!(backwards, [7, 6, 5, 4, 2]);
+        assert!(list.try_extend_from_slice(&[]).is_ok());
+    }
+}
@@ -0,0 +1,588 @@
+use std::cell::{RefCell, RefMut};
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Safe) Implementation
+// Impl: Rc<RefCell<Node>> + Weak back-pointer
+// ========================================================
+
+// Node definition
+struct Node<T> {
+    value: T,
+    next: Option<Rc<RefCell<Node<T>>>>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+}
+
+pub struct DoublyLinkedList<T = i32> {
+    head: Option<Rc<RefCell<Node<T>>>>,
+    tail: Option<Rc<RefCell<Node<T>>>>,
+    len: usize,
+    // 最後にカーソルがいた位置。None は末尾の外（ghost）で、そのとき cursor_index == len
+    cursor: Option<Rc<RefCell<Node<T>>>>,
+    cursor_index: usize,
+}
+
+pub struct DllIter<'a, T = i32> {
+    current: Option<Rc<RefCell<Node<T>>>>,
+    _marker: PhantomData<&'a DoublyLinkedList<T>>,
+}
+
+impl<T: Clone> Iterator for DllIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.current.take()?;
+        let borrowed = node.borrow();
+        self.current = borrowed.next.clone();
+        Some(borrowed.value.clone())
+    }
+}
+
+/// 位置を覚えておくカーソル（`UnsafeDllCursorMut` のRc<RefCell>版）。
+///
+/// 要素を指しているか、末尾と先頭の間にある「ghost」（`index() == None`）を指す。
+/// 位置はリスト側に保存されるので、カーソルを作り直しても前回の位置から再開する。
+pub struct DllCursorMut<'a, T = i32> {
+    list: &'a mut DoublyLinkedList<T>,
+}
+
+impl<T> DoublyLinkedList<T> {
+    /// 要素を持たない空のリスト
+    pub const fn new_empty() -> Self {
+        Self { head: None, tail: None, len: 0, cursor: None, cursor_index: 0 }
+    }
+
+    /// 前回の位置から再開するカーソル
+    pub fn cursor_mut(&mut self) -> DllCursorMut<'_, T> {
+        DllCursorMut { list: self }
+    }
+
+    /// `index` の位置（`index >= len` なら ghost）に合わせたカーソル
+    pub fn cursor_at_mut(&mut self, index: usize) -> DllCursorMut<'_, T> {
+        let mut cursor = self.cursor_mut();
+        cursor.seek(index);
+        cursor
+    }
+
+    // index番目のノードを、head・tail・カーソルのうち一番近いところから探す
+    fn node_at(&self, index: usize) -> Option<Rc<RefCell<Node<T>>>> {
+        if index >= self.len {
+            return None;
+        }
+        let mut start = (self.head.as_ref(), 0);
+        if self.len - 1 - index < index {
+            start = (self.tail.as_ref(), self.len - 1);
+        }
+        if self.cursor.is_some() && self.cursor_index.abs_diff(index) < start.1.abs_diff(index) {
+            start = (self.cursor.as_ref(), self.cursor_index);
+        }
+        let (node, mut i) = start;
+        let mut current = Rc::clone(node?);
+        while i < index {
+            let next = current.borrow().next.clone()?;
+            current = next;
+            i += 1;
+        }
+        while i > index {
+            let prev = current.borrow().prev.as_ref().and_then(Weak::upgrade)?;
+            current = prev;
+            i -= 1;
+        }
+        Some(current)
+    }
+
+    // nodeをリストから切り離し、前後を繋ぎ直して値を取り出す
+    // (nodeへの強参照は呼び出し側が持つ1つだけにしておくこと。カーソルの調整は呼び出し側)
+    fn unlink(&mut self, node: Rc<RefCell<Node<T>>>) -> T {
+        self.len -= 1;
+
+        // 前後のリンクを奪ってから繋ぎ直す
+        let prev = node.borrow_mut().prev.take().and_then(|w| w.upgrade());
+        let next = node.borrow_mut().next.take();
+
+        match &prev {
+            Some(p) => p.borrow_mut().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match &next {
+            Some(n) => n.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev,
+        }
+
+        // 他から参照されなくなったので、Cloneせずに値を取り出せる
+        match Rc::try_unwrap(node) {
+            Ok(cell) => cell.into_inner().value,
+            Err(_) => unreachable!("unlinked node is still shared"),
+        }
+    }
+
+    // prev と next の間に新しいノードを繋ぐ（どちらかが None なら端）
+    fn link_between(
+        &mut self,
+        prev: Option<Rc<RefCell<Node<T>>>>,
+        next: Option<Rc<RefCell<Node<T>>>>,
+        value: T,
+    ) {
+        self.len += 1;
+        let new_node = Rc::new(RefCell::new(Node {
+            value,
+            next: next.clone(),
+            prev: prev.as_ref().map(Rc::downgrade),
+        }));
+        match prev {
+            Some(p) => p.borrow_mut().next = Some(Rc::clone(&new_node)),
+            None => self.head = Some(Rc::clone(&new_node)),
+        }
+        match next {
+            Some(n) => n.borrow_mut().prev = Some(Rc::downgrade(&new_node)),
+            None => self.tail = Some(new_node),
+        }
+    }
+
+    // first から末尾までを新しいリストに移す（first は kept 番目のノード。None なら空）
+    // カーソルが移した側にあった場合は ghost に戻す
+    fn split_off_from(&mut self, first: Option<Rc<RefCell<Node<T>>>>, kept: usize) -> Self {
+        let mut rest = Self::new_empty();
+        let Some(first) = first else {
+            return rest;
+        };
+        let prev = first.borrow_mut().prev.take().and_then(|w| w.upgrade());
+        match &prev {
+            Some(p) => p.borrow_mut().next = None,
+            None => self.head = None,
+        }
+        rest.tail = std::mem::replace(&mut self.tail, prev);
+        rest.head = Some(first);
+        rest.len = self.len - kept;
+        rest.cursor_index = rest.len;
+        self.len = kept;
+        if self.cursor.is_none() || self.cursor_index >= kept {
+            self.cursor = None;
+            self.cursor_index = kept;
+        }
+        rest
+    }
+}
+
+impl<T> DllCursorMut<'_, T> {
+    /// 指している要素の位置（ghost なら `None`）
+    pub fn index(&self) -> Option<usize> {
+        self.list.cursor.as_ref().map(|_| self.list.cursor_index)
+    }
+
+    pub fn current(&mut self) -> Option<RefMut<'_, T>> {
+        let node = self.list.cursor.as_ref()?;
+        Some(RefMut::map(node.borrow_mut(), |node| &mut node.value))
+    }
+
+    /// 次の要素へ進む（末尾の次は ghost、ghost の次は先頭）
+    pub fn move_next(&mut self) {
+        let list = &mut *self.list;
+        match list.cursor.take() {
+            Some(node) => {
+                list.cursor = node.borrow().next.clone();
+                list.cursor_index += 1;
+            }
+            None => {
+                list.cursor = list.head.clone();
+                list.cursor_index = 0;
+            }
+        }
+    }
+
+    /// 前の要素へ戻る（先頭の前は ghost、ghost の前は末尾）
+    pub fn move_prev(&mut self) {
+        let list = &mut *self.list;
+        match list.cursor.take() {
+            Some(node) => {
+                list.cursor = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+                if list.cursor.is_some() {
+                    list.cursor_index -= 1;
+                } else {
+                    list.cursor_index = list.len;
+                }
+            }
+            None => {
+                list.cursor = list.tail.clone();
+                list.cursor_index = list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// `index` 番目へ移動する（`index >= len` なら ghost）。
+    /// head・tail・今の位置のうち一番近いところから辿る。
+    pub fn seek(&mut self, index: usize) {
+        let list = &mut *self.list;
+        list.cursor = list.node_at(index);
+        list.cursor_index = index.min(list.len);
+    }
+
+    /// 今の要素の手前に挿入する（ghost なら末尾に追加）。カーソルは今の要素のまま。
+    pub fn insert_before(&mut self, value: T) {
+        let list = &mut *self.list;
+        let next = list.cursor.clone();
+        let prev = match &next {
+            Some(node) => node.borrow().prev.as_ref().and_then(Weak::upgrade),
+            None => list.tail.clone(),
+        };
+        list.link_between(prev, next, value);
+        // 手前に1つ増えたので、今の要素（または ghost）の位置は1つ後ろになる
+        list.cursor_index += 1;
+    }
+
+    /// 今の要素の後ろに挿入する（ghost なら先頭に追加）。カーソルは今の要素のまま。
+    pub fn insert_after(&mut self, value: T) {
+        let list = &mut *self.list;
+        let prev = list.cursor.clone();
+        let next = match &prev {
+            Some(node) => node.borrow().next.clone(),
+            None => list.head.clone(),
+        };
+        list.link_between(prev, next, value);
+        if list.cursor.is_none() {
+            list.cursor_index = list.len;
+        }
+    }
+
+    /// 今の要素を取り除いて返し、次の要素へ進む（ghost なら `None`）
+    pub fn remove_current(&mut self) -> Option<T> {
+        let list = &mut *self.list;
+        let node = list.cursor.take()?;
+        list.cursor = node.borrow().next.clone();
+        Some(list.unlink(node))
+    }
+
+    /// 今の要素より後ろを切り離して新しいリストとして返す（ghost なら全体）
+    pub fn split_after(&mut self) -> DoublyLinkedList<T> {
+        let list = &mut *self.list;
+        let (first, kept) = match &list.cursor {
+            Some(node) => (node.borrow().next.clone(), list.cursor_index + 1),
+            None => (list.head.clone(), 0),
+        };
+        list.split_off_from(first, kept)
+    }
+
+    /// 今の要素より前を切り離して新しいリストとして返す（ghost なら全体）
+    pub fn split_before(&mut self) -> DoublyLinkedList<T> {
+        let list = &mut *self.list;
+        let Some(cursor) = list.cursor.clone() else {
+            return std::mem::replace(list, DoublyLinkedList::new_empty());
+        };
+        // 今の要素から後ろを切り離して入れ替えれば、手前だけが残る
+        let index = list.cursor_index;
+        let mut rest = list.split_off_from(Some(Rc::clone(&cursor)), index);
+        rest.cursor = Some(cursor);
+        rest.cursor_index = 0;
+        std::mem::swap(list, &mut rest);
+        rest
+    }
+}
+
+impl<T: Clone> SequenceBackend<T> for DoublyLinkedList<T> {
+    type Iter<'a> = DllIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::new_empty()
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Self::new()
+    }
+
+    fn append(&mut self, value: T) {
+        let tail = self.tail.clone();
+        self.link_between(tail, None, value);
+        // カーソルが ghost なら ghost のまま（位置は len に揃える）
+        if self.cursor.is_none() {
+            self.cursor_index = self.len;
+        }
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        // 後ろから作ると各ノードは next を持って生まれるので、tail の clone や
+        // ノードごとの繋ぎ替えをせず、確保と前向きリンクを1パスで済ませられる。
+        // 最後に出来た鎖を1回だけ tail に繋ぐ
+        let mut first: Option<Rc<RefCell<Node<T>>>> = None;
+        let mut last = None;
+        for value in values.iter().rev() {
+            let node = Rc::new(RefCell::new(Node { value: value.clone(), next: first.take(), prev: None }));
+            match &node.borrow().next {
+                Some(next) => next.borrow_mut().prev = Some(Rc::downgrade(&node)),
+                None => last = Some(Rc::clone(&node)),
+            }
+            first = Some(node);
+        }
+        let Some(first) = first else {
+            return;
+        };
+        match self.tail.take() {
+            Some(tail) => {
+                first.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(first);
+            }
+            None => self.head = Some(first),
+        }
+        self.tail = last;
+        self.len += values.len();
+        // カーソルが ghost なら ghost のまま
+        if self.cursor.is_none() {
+            self.cursor_index = self.len;
+        }
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        // 以前は毎回headから辿っていた (O(N))。今はカーソルを一番近い端か
+        // 前回の位置から動かすので、近くへの連続した挿入はO(1)になる
+        // (末尾(範囲外含む)は ghost の手前 = append)
+        self.cursor_at_mut(index).insert_before(value);
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        self.cursor_at_mut(index).remove_current()
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        self.node_at(index).map(|node| node.borrow().value.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> DllIter<'_, T> {
+        DllIter {
+            current: self.head.clone(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.release();
+    }
+}
+
+impl<T> DoublyLinkedList<T> {
+    // Dropからも呼ぶので、要素型の制約（T: Clone）なしで書く
+    fn release(&mut self) {
+        // headから順に所有権を奪っていく（take）
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            // nodeの借用がここで終わるようにブロックを作るか、単にnextをtakeする
+            // currentのnextを奪い取ることで、再帰的な連鎖を断ち切る
+            current = node.borrow_mut().next.take();
+            // ここで `node` (Rc) がスコープを抜け、参照カウントが減って破棄される。
+            // しかし next はすでに None になっているので、再帰は起きない。
+        }
+        self.tail = None;
+        self.len = 0;
+        self.cursor = None;
+        self.cursor_index = 0;
+    }
+}
+
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    // prev/next と tail・len、カーソルの位置が実際のノードと一致することを確かめてから、中身を比べる
+    fn check(list: &DoublyLinkedList, expected: &[i32]) {
+        let mut prev: Option<Rc<RefCell<Node<i32>>>> = None;
+        let mut current = list.head.clone();
+        let mut count = 0;
+        while let Some(node) = current {
+            let back = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+            assert!(back.as_ref().map(Rc::as_ptr) == prev.as_ref().map(Rc::as_ptr), "prev link broken at {}", count);
+            if list.cursor.as_ref().is_some_and(|c| Rc::ptr_eq(c, &node)) {
+                assert_eq!(list.cursor_index, count, "cursor_index does not match the cursor node");
+            }
+            current = node.borrow().next.clone();
+            prev = Some(node);
+            count += 1;
+        }
+        assert!(list.tail.as_ref().map(Rc::as_ptr) == prev.as_ref().map(Rc::as_ptr));
+        assert_eq!(count, list.len);
+        if list.cursor.is_none() {
+            assert_eq!(list.cursor_index, list.len, "ghost cursor must sit at len");
+        }
+        assert!(list.iter().eq(expected.iter().copied()));
+    }
+
+    #[test]
+    fn moves_wrap_around_through_the_ghost() {
+        let mut list: DoublyLinkedList = DoublyLinkedList::new();
+        list.extend_from_slice(&[1, 2, 3]);
+        let mut cursor = list.cursor_at_mut(0);
+        assert_eq!(cursor.index(), Some(0));
+        // 先頭の前は ghost、ghost の前は末尾
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert!(cursor.current().is_none());
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().map(|v| *v), Some(3));
+        // 末尾の次は ghost、ghost の次は先頭
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        *cursor.current().unwrap() = 10;
+        check(&list, &[10, 2, 3]);
+
+        let mut empty: DoublyLinkedList = DoublyLinkedList::new();
+        let mut cursor = empty.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        check(&empty, &[]);
+    }
+
+    #[test]
+    fn inserts_keep_the_cursor_on_its_element_or_the_ghost() {
+        let mut list: DoublyLinkedList = DoublyLinkedList::new();
+        let mut cursor = list.cursor_mut();
+        // ghost の後ろは先頭、手前は末尾
+        cursor.insert_after(2);
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+        assert_eq!(cursor.index(), None);
+        check(&list, &[1, 2, 3]);
+
+        let mut cursor = list.cursor_at_mut(1);
+        cursor.insert_before(5);
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_after(6);
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().map(|v| *v), Some(2));
+        check(&list, &[1, 5, 2, 6, 3]);
+
+        // 続けて使うと前回の位置から再開する
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.current().map(|v| *v), Some(6));
+        check(&list, &[1, 5, 2, 6, 3]);
+    }
+
+    #[test]
+    fn remove_current_moves_to_the_next_element() {
+        let mut list: DoublyLinkedList = DoublyLinkedList::new();
+        list.extend_from_slice(&[1, 2, 3]);
+        let mut cursor = list.cursor_at_mut(1);
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.index(), Some(1));
+        // 末尾を消すと ghost へ
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        check(&list, &[1]);
+
+        let mut cursor = list.cursor_at_mut(0);
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.index(), None);
+        check(&list, &[]);
+    }
+
+    #[test]
+    fn split_after_keeps_the_cursor_in_the_front_half() {
+        let mut list: DoublyLinkedList = DoublyLinkedList::new();
+        list.extend_from_slice(&[0, 1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_at_mut(2);
+        let mut back = cursor.split_after();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().map(|v| *v), Some(2));
+        check(&list, &[0, 1, 2]);
+        // 切り離した側のカーソルは ghost
+        assert_eq!(back.cursor_mut().index(), None);
+        check(&back, &[3, 4, 5]);
+
+        // どちらもそのまま使える
+        list.append(9);
+        back.insert(0, 8);
+        check(&list, &[0, 1, 2, 9]);
+        check(&back, &[8, 3, 4, 5]);
+
+        // 末尾で切ると空、ghost で切ると全体
+        let mut cursor = list.cursor_at_mut(3);
+        assert_eq!(cursor.split_after().len(), 0);
+        let mut cursor = list.cursor_at_mut(usize::MAX);
+        let all = cursor.split_after();
+        check(&list, &[]);
+        check(&all, &[0, 1, 2, 9]);
+    }
+
+    #[test]
+    fn split_before_leaves_the_cursor_at_the_new_head() {
+        let mut list: DoublyLinkedList = DoublyLinkedList::new();
+        list.extend_from_slice(&[0, 1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_at_mut(3);
+        let mut front = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current().map(|v| *v), Some(3));
+        check(&list, &[3, 4, 5]);
+        // カーソルは切り離した側にいたので、前半は ghost に戻る
+        assert_eq!(front.cursor_mut().index(), None);
+        check(&front, &[0, 1, 2]);
+
+        front.append(7);
+        list.insert(1, 6);
+        check(&front, &[0, 1, 2, 7]);
+        check(&list, &[3, 6, 4, 5]);
+
+        // 先頭で切ると空、ghost で切ると全体
+        let mut cursor = list.cursor_at_mut(0);
+        assert_eq!(cursor.split_before().len(), 0);
+        check(&list, &[3, 6, 4, 5]);
+        let mut cursor = list.cursor_at_mut(4);
+        let all = cursor.split_before();
+        check(&list, &[]);
+        check(&all, &[3, 6, 4, 5]);
+    }
+
+    #[test]
+    fn both_halves_drop_their_elements() {
+        let token = Rc::new(());
+        let mut list: DoublyLinkedList<Rc<()>> = DoublyLinkedList::new();
+        for _ in 0..6 {
+            list.append(token.clone());
+        }
+        let back = list.cursor_at_mut(3).split_after();
+        let front = list.cursor_at_mut(1).split_before();
+        assert_eq!((front.len(), list.len(), back.len()), (1, 3, 2));
+        assert_eq!(Rc::strong_count(&token), 7);
+        drop(front);
+        drop(back);
+        assert_eq!(Rc::strong_count(&token), 4);
+        drop(list);
+        assert_eq!(Rc::strong_count(&token), 1);
+    }
+}
@@ -0,0 +1,325 @@
+use std::iter::Sum;
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Gap Buffer) Implementation
+// Impl: One contiguous buffer with a movable gap (ptr::copy = memmove)
+// ========================================================
+
+// [0, gap_start) と [gap_end, cap) に要素が入っていて、その間が空き（ギャップ）。
+// ギャップの位置がカーソルになる。UnsafeZipperList と違って右側を逆順に持たないので、
+// ギャップの移動は1回の memmove で済む。
+
+pub struct GapBuffer<T = i32> {
+    buf: Vec<MaybeUninit<T>>, // len() == capacity として使う（初期化済みかどうかはギャップで管理）
+    gap_start: usize,
+    gap_end: usize,
+}
+
+/// ギャップの前を読んでから、ギャップの後ろを読む
+pub type GapBufferIter<'a, T = i32> =
+    std::iter::Cloned<std::iter::Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>>;
+
+impl<T> GapBuffer<T> {
+    fn with_buffer(capacity: usize) -> Self {
+        Self { buf: uninit_buffer(capacity), gap_start: 0, gap_end: capacity }
+    }
+
+    /// 確保済みの要素数（ギャップを含む）
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    // ギャップより前の要素
+    fn front(&self) -> &[T] {
+        // [0, gap_start) は初期化済み
+        unsafe { std::slice::from_raw_parts(self.buf.as_ptr() as *const T, self.gap_start) }
+    }
+
+    // ギャップより後ろの要素
+    fn back(&self) -> &[T] {
+        // [gap_end, cap) は初期化済み
+        unsafe {
+            std::slice::from_raw_parts(self.buf.as_ptr().add(self.gap_end) as *const T, self.buf.len() - self.gap_end)
+        }
+    }
+
+    // ギャップを index の位置へ移動する（index は 0..=len）
+    // 間にある要素を ptr::copy (memmove) でまとめてギャップの反対側へ移す
+    #[inline(always)]
+    fn move_gap(&mut self, index: usize) {
+        let base = self.buf.as_mut_ptr();
+        unsafe {
+            if index < self.gap_start {
+                // [index, gap_start) をギャップの後ろ側へ
+                let count = self.gap_start - index;
+                ptr::copy(base.add(index), base.add(self.gap_end - count), count);
+                self.gap_start -= count;
+                self.gap_end -= count;
+            } else if index > self.gap_start {
+                // ギャップの後ろの先頭 count 個を前側へ
+                let count = index - self.gap_start;
+                ptr::copy(base.add(self.gap_end), base.add(self.gap_start), count);
+                self.gap_start += count;
+                self.gap_end += count;
+            }
+        }
+    }
+
+    // 倍の大きさ（ギャップが additional 個に足りなければそれ以上）のバッファへ移す
+    // （前側・後ろ側をそれぞれ1回でコピー）
+    #[cold]
+    fn grow(&mut self, additional: usize) {
+        let old_cap = self.buf.len();
+        let new_cap = (old_cap * 2).max(8).max(old_cap - self.gap_len() + additional);
+        let back_len = old_cap - self.gap_end;
+        let mut new_buf = uninit_buffer(new_cap);
+        unsafe {
+            let src = self.buf.as_ptr();
+            let dst = new_buf.as_mut_ptr();
+            ptr::copy_nonoverlapping(src, dst, self.gap_start);
+            ptr::copy_nonoverlapping(src.add(self.gap_end), dst.add(new_cap - back_len), back_len);
+        }
+        // 古いバッファは MaybeUninit なので中身はDropされない（要素は新しい方へ移動済み）
+        self.buf = new_buf;
+        self.gap_end = new_cap - back_len;
+    }
+
+    // ギャップの位置に値を書き込む
+    #[inline(always)]
+    fn push_at_gap(&mut self, value: T) {
+        if self.gap_start == self.gap_end {
+            self.grow(1);
+        }
+        // ここではギャップが1つ以上空いている
+        unsafe { (*self.buf.as_mut_ptr().add(self.gap_start)).write(value) };
+        self.gap_start += 1;
+    }
+
+    // 中身をすべてDropして空にする
+    fn drop_elements(&mut self) {
+        unsafe {
+            let base = self.buf.as_mut_ptr() as *mut T;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base, self.gap_start));
+            let back_len = self.buf.len() - self.gap_end;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base.add(self.gap_end), back_len));
+        }
+        self.gap_start = 0;
+        self.gap_end = self.buf.len();
+    }
+}
+
+// 未初期化のまま capacity 個分の長さを持つバッファ（要素ごとの初期化ループを避ける）
+fn uninit_buffer<T>(capacity: usize) -> Vec<MaybeUninit<T>> {
+    let mut buf = Vec::with_capacity(capacity);
+    // MaybeUninit は未初期化のままで有効な値
+    unsafe { buf.set_len(capacity) };
+    buf
+}
+
+impl<T: Clone> SequenceBackend<T> for GapBuffer<T> {
+    type Iter<'a> = GapBufferIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_buffer(capacity)
+    }
+
+    fn append(&mut self, value: T) {
+        let len = self.len();
+        self.move_gap(len);
+        self.push_at_gap(value);
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        let len = self.len();
+        self.move_gap(len);
+        if self.gap_len() < values.len() {
+            self.grow(values.len());
+        }
+        // ギャップの先頭から詰めて書く（途中で clone が panic しても、書いた分がリークするだけ）
+        let gap = &mut self.buf[self.gap_start..self.gap_start + values.len()];
+        for (slot, value) in gap.iter_mut().zip(values) {
+            slot.write(value.clone());
+        }
+        self.gap_start += values.len();
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn insert(&mut self, index: usize, value: T) {
+        self.move_gap(index.min(self.len()));
+        self.push_at_gap(value);
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        // ギャップを index に合わせると、対象はギャップの直後
+        self.move_gap(index);
+        let value = unsafe { self.buf[self.gap_end].assume_init_read() };
+        self.gap_end += 1;
+        Some(value)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len() {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        if index < self.gap_start {
+            Some(self.front()[index].clone())
+        } else {
+            self.back().get(index - self.gap_start).cloned()
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.buf.len() - self.gap_len()
+    }
+
+    fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        let front_sum: T = self.front().iter().cloned().sum();
+        let back_sum: T = self.back().iter().cloned().sum();
+        [front_sum, back_sum].into_iter().sum()
+    }
+
+    fn iter(&self) -> GapBufferIter<'_, T> {
+        self.front().iter().chain(self.back().iter()).cloned()
+    }
+
+    fn clear(&mut self) {
+        self.drop_elements();
+    }
+}
+
+impl<T> Drop for GapBuffer<T> {
+    fn drop(&mut self) {
+        self.drop_elements();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::diff;
+    use crate::workload::{OpMix, PositionDist, Workload};
+    use crate::BackendKind;
+
+    fn check(list: &GapBuffer, model: &[i32]) {
+        assert!(list.gap_start <= list.gap_end && list.gap_end <= list.capacity());
+        assert_eq!(list.len(), model.len());
+        assert!(list.iter().eq(model.iter().copied()));
+        for (i, v) in model.iter().enumerate() {
+            assert_eq!(list.get(i), Some(*v));
+        }
+        assert_eq!(list.get(model.len()), None);
+    }
+
+    // 位置の分布ごとにギャップの動き方が変わる（Sequential(1) は直前の編集位置の前後＝ギャップの端を狙う）
+    #[test]
+    fn random_ops_match_the_oracle() {
+        let mix = OpMix { append: 1, insert: 4, remove: 3, get: 1, sum: 1 };
+        for dist in [PositionDist::Head, PositionDist::Tail, PositionDist::Sequential(1), PositionDist::Uniform] {
+            let ops = Workload::new(2000, 0x2545_f491, mix, dist).with_prefill(20).generate();
+            if let Err(d) = diff::check_backend(BackendKind::GapBuffer, &ops) {
+                panic!("{}", d);
+            }
+        }
+    }
+
+    #[test]
+    fn move_gap_keeps_the_order() {
+        let mut list = GapBuffer::from_slice(&(0..10).collect::<Vec<i32>>());
+        let model: Vec<i32> = (0..10).collect();
+        for index in (0..=10).chain((0..=10).rev()).chain([3, 7, 0, 10, 5]) {
+            list.move_gap(index);
+            assert_eq!(list.gap_start, index);
+            check(&list, &model);
+        }
+    }
+
+    #[test]
+    fn grow_keeps_both_sides_of_the_gap() {
+        let mut list: GapBuffer = GapBuffer::with_capacity(4);
+        let mut model = vec![0, 1, 2, 3];
+        list.extend_from_slice(&model);
+        // ギャップを真ん中に置いたまま溢れさせる
+        list.insert(2, 9);
+        model.insert(2, 9);
+        assert_eq!(list.capacity(), 8);
+        assert_eq!(list.gap_start, 3);
+        check(&list, &model);
+
+        // 倍でも足りなければ必要な分まで
+        let more: Vec<i32> = (10..30).collect();
+        list.extend_from_slice(&more);
+        model.extend_from_slice(&more);
+        assert_eq!(list.capacity(), 25);
+        check(&list, &model);
+
+        // 容量0から
+        let mut empty: GapBuffer = GapBuffer::new();
+        empty.insert(0, 1);
+        assert_eq!(empty.capacity(), 8);
+        check(&empty, &[1]);
+    }
+
+    #[test]
+    fn drops_every_non_copy_element_exactly_once() {
+        let token = Rc::new(());
+        let mut list: GapBuffer<Rc<()>> = GapBuffer::with_capacity(1);
+        for i in 0..20 {
+            list.insert(i / 2, token.clone());
+        }
+        assert_eq!(Rc::strong_count(&token), 21);
+        // ギャップを真ん中に置いて前後どちらにも要素がある状態
+        drop(list.remove(5));
+        drop(list.get(12));
+        assert_eq!(Rc::strong_count(&token), 20);
+
+        list.clear();
+        assert_eq!(Rc::strong_count(&token), 1);
+        assert!(list.is_empty());
+
+        list.extend_from_slice(&[token.clone(), token.clone()]);
+        list.insert(1, token.clone());
+        list.move_gap(1);
+        drop(list);
+        assert_eq!(Rc::strong_count(&token), 1);
+
+        let mut words: GapBuffer<String> = GapBuffer::new();
+        for w in ["b", "d", "a"] {
+            words.insert(1, w.to_string());
+        }
+        assert_eq!(words.remove(0).as_deref(), Some("b"));
+        assert_eq!(words.iter().collect::<Vec<_>>(), ["a", "d"]);
+    }
+}
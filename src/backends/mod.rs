@@ -0,0 +1,206 @@
+// ========================================================
+// List Backends
+// 全ての実装は `SequenceBackend` を実装する
+// ========================================================
+
+use crate::sequence::DynSequence;
+use crate::SequenceBackend;
+
+mod arena;
+mod arena_dll;
+mod bump;
+mod dll;
+mod gap_buffer;
+mod skiplist;
+mod soa_bump;
+mod treap;
+mod unrolled;
+mod unsafe_dll;
+mod unsafe_zipper;
+mod zipper;
+
+pub use arena::{AllocError, BumpArena};
+pub use arena_dll::{ArenaDll, ArenaDllIter, NodeHandle};
+pub use bump::{BumpDll, BumpDllIter, GrowableBumpDll};
+pub use dll::{DllCursorMut, DllIter, DoublyLinkedList};
+pub use gap_buffer::{GapBuffer, GapBufferIter};
+pub use skiplist::{SkipList, SkipListIter, DEFAULT_LEVEL_SEED};
+pub use soa_bump::{SoaBumpDll, SoaBumpDllIter};
+pub use treap::{Measured, TreapIter, TreapList};
+pub use unrolled::{UnrolledIter, UnrolledList, DEFAULT_BLOCK_SIZE};
+pub use unsafe_dll::{UnsafeDll, UnsafeDllCursorMut, UnsafeDllIter};
+pub use unsafe_zipper::UnsafeZipperList;
+pub use zipper::{ZipperIter, ZipperList};
+
+/// 名前（Python/JS/CLIからの指定）で選べるバックエンドの一覧
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BackendKind {
+    Safe,
+    Unsafe,
+    Bump,
+    /// チャンクを継ぎ足すバンプアロケータ（大きなN用）
+    BumpGrowable,
+    Zipper,
+    UnsafeZipper,
+    /// 1本のバッファとギャップ（ptr::copyでギャップを動かす）
+    GapBuffer,
+    /// 固定長配列のブロックを繋いだDLL（ブロックサイズは既定値）
+    Unrolled,
+    /// 位置をキーにした平衡二分木（挿入・削除が O(log N)）
+    Treap,
+    /// 幅付きの前向きリンクを持つスキップリスト（挿入・削除が期待 O(log N)、シードは既定値）
+    SkipList,
+    /// Vec に並べたノードを u32 の添字で繋いだDLL（unsafe なし、世代付きハンドル）
+    ArenaDll,
+    /// value / next / prev を別々の配列に持つバンプリスト（SoA）
+    SoaBump,
+}
+
+impl BackendKind {
+    pub const ALL: &'static [BackendKind] = &[
+        BackendKind::Safe,
+        BackendKind::Unsafe,
+        BackendKind::Bump,
+        BackendKind::BumpGrowable,
+        BackendKind::Zipper,
+        BackendKind::UnsafeZipper,
+        BackendKind::GapBuffer,
+        BackendKind::Unrolled,
+        BackendKind::Treap,
+        BackendKind::SkipList,
+        BackendKind::ArenaDll,
+        BackendKind::SoaBump,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BackendKind::Safe => "safe",
+            BackendKind::Unsafe => "unsafe",
+            BackendKind::Bump => "bump",
+            BackendKind::BumpGrowable => "bump-growable",
+            BackendKind::Zipper => "zipper",
+            BackendKind::UnsafeZipper => "unsafe-zipper",
+            BackendKind::GapBuffer => "gap-buffer",
+            BackendKind::Unrolled => "unrolled",
+            BackendKind::Treap => "treap",
+            BackendKind::SkipList => "skiplist",
+            BackendKind::ArenaDll => "arena-dll",
+            BackendKind::SoaBump => "soa-bump",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BackendKind> {
+        Self::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+
+    /// 実行時に選んだ実装を空のリストとして作る
+    pub fn boxed(self) -> Box<dyn DynSequence> {
+        crate::with_backend!(self, B => Box::new(B::new()))
+    }
+}
+
+/// `BackendKind` の値に対応する具体型を `$B` として `$body` を展開する。
+/// `$B<$T>` と書くと要素型 `$T` のリストになる（`$T` は具体型であること）。
+///
+/// ```ignore
+/// let sum = with_backend!(kind, B => run_append_sum::<B>(1000));
+/// let sum = with_backend!(kind, B<f64> => append_sum::<f64, B>(&samples));
+/// ```
+#[macro_export]
+macro_rules! with_backend {
+    ($kind:expr, $B:ident => $body:expr) => {
+        $crate::with_backend!($kind, $B<i32> => $body)
+    };
+    ($kind:expr, $B:ident<$T:ty> => $body:expr) => {
+        match $kind {
+            $crate::backends::BackendKind::Safe => {
+                type $B = $crate::backends::DoublyLinkedList<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::Unsafe => {
+                type $B = $crate::backends::UnsafeDll<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::Bump => {
+                type $B = $crate::backends::BumpDll<'static, $T>;
+                $body
+            }
+            $crate::backends::BackendKind::BumpGrowable => {
+                type $B = $crate::backends::GrowableBumpDll<'static, $T>;
+                $body
+            }
+            $crate::backends::BackendKind::Zipper => {
+                type $B = $crate::backends::ZipperList<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::UnsafeZipper => {
+                type $B = $crate::backends::UnsafeZipperList<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::GapBuffer => {
+                type $B = $crate::backends::GapBuffer<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::Unrolled => {
+                type $B = $crate::backends::UnrolledList<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::Treap => {
+                type $B = $crate::backends::TreapList<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::SkipList => {
+                type $B = $crate::backends::SkipList<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::ArenaDll => {
+                type $B = $crate::backends::ArenaDll<$T>;
+                $body
+            }
+            $crate::backends::BackendKind::SoaBump => {
+                type $B = $crate::backends::SoaBumpDll<$T>;
+                $body
+            }
+        }
+    };
+}
+
+// ========================================================
+// FromIterator / Extend
+// 一括構築（`from_slice` / `extend_from_slice`）を標準のトレイトからも使えるようにする
+// ========================================================
+
+// イテレータは一度 Vec に集めてからスライスとして渡す（長さが分かってから確保・コピーできる）
+// BumpDll はライフタイムと const 引数を持つので bump.rs に直接書いている
+macro_rules! impl_bulk_traits {
+    ($($list:ident<T: $bound:path>),* $(,)?) => {
+        $(
+            impl<T: $bound> FromIterator<T> for $list<T> {
+                fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                    let values: Vec<T> = iter.into_iter().collect();
+                    <Self as SequenceBackend<T>>::from_slice(&values)
+                }
+            }
+
+            impl<T: $bound> Extend<T> for $list<T> {
+                fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+                    let values: Vec<T> = iter.into_iter().collect();
+                    self.extend_from_slice(&values);
+                }
+            }
+        )*
+    };
+}
+
+impl_bulk_traits!(
+    DoublyLinkedList<T: Clone>,
+    UnsafeDll<T: Clone>,
+    ZipperList<T: Clone>,
+    UnsafeZipperList<T: Clone>,
+    GapBuffer<T: Clone>,
+    UnrolledList<T: Clone>,
+    TreapList<T: Measured>,
+    SkipList<T: Clone>,
+    ArenaDll<T: Clone>,
+    SoaBumpDll<T: Copy>,
+);
@@ -0,0 +1,419 @@
+use std::marker::PhantomData;
+
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Indexable Skip List) Implementation
+// Impl: Raw-pointer skip list with widths on forward pointers
+// ========================================================
+
+// 各ノードが確率的に決まる高さ分の前向きリンクを持ち、リンクごとに
+// 「何要素先へ飛ぶか（width）」を覚えておく。上のレベルから width を足しながら
+// 降りていけば、位置での検索・挿入・削除が期待 O(log N) になる。
+// 位置は head を 0、i番目の要素を i+1 として数える。
+
+/// レベルの上限（2^32要素まで想定）
+const MAX_LEVEL: usize = 32;
+
+/// シードを指定しないときの値（`run_*_insert_py` のLCGと同じ）
+pub const DEFAULT_LEVEL_SEED: u64 = 123456789;
+
+struct Link<T> {
+    next: *mut SkipNode<T>,
+    width: usize, // next までの距離（next が null のときは使わない）
+}
+
+impl<T> Clone for Link<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Link<T> {}
+
+impl<T> Link<T> {
+    const NULL: Link<T> = Link { next: std::ptr::null_mut(), width: 0 };
+}
+
+struct SkipNode<T> {
+    value: T,
+    links: Box<[Link<T>]>, // 長さ = このノードの高さ
+}
+
+pub struct SkipList<T = i32> {
+    head: [Link<T>; MAX_LEVEL],
+    level: usize, // 使っているレベル数
+    len: usize,
+    rng: u64, // レベル決め用の xorshift64（同じシードなら同じ形になる）
+}
+
+pub struct SkipListIter<'a, T = i32> {
+    current: *mut SkipNode<T>,
+    _marker: PhantomData<&'a SkipList<T>>,
+}
+
+impl<T: Clone> Iterator for SkipListIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = unsafe { self.current.as_ref()? };
+        self.current = node.links[0].next;
+        Some(node.value.clone())
+    }
+}
+
+impl<T> SkipList<T> {
+    /// レベルの乱数列を `seed` で決めたリスト
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            head: [Link::NULL; MAX_LEVEL],
+            level: 0,
+            len: 0,
+            // xorshiftは0から抜け出せないので避ける
+            rng: seed.max(1),
+        }
+    }
+
+    /// 今使っているレベル数
+    pub fn levels(&self) -> usize {
+        self.level
+    }
+
+    // 1/2 の確率で1段ずつ高くする
+    fn random_level(&mut self) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (1 + self.rng.trailing_ones() as usize).min(MAX_LEVEL)
+    }
+
+    // node（null なら head）のレベル l のリンク
+    unsafe fn link(&mut self, node: *mut SkipNode<T>, l: usize) -> &mut Link<T> {
+        if node.is_null() {
+            &mut self.head[l]
+        } else {
+            &mut (*node).links[l]
+        }
+    }
+
+    // 各レベルで「位置が target 未満の最後のノード」とその位置を集める（null は head）
+    fn predecessors(&mut self, target: usize) -> ([*mut SkipNode<T>; MAX_LEVEL], [usize; MAX_LEVEL]) {
+        let mut update = [std::ptr::null_mut(); MAX_LEVEL];
+        let mut positions = [0; MAX_LEVEL];
+        let mut node: *mut SkipNode<T> = std::ptr::null_mut();
+        let mut pos = 0;
+        for l in (0..self.level).rev() {
+            unsafe {
+                loop {
+                    let link = *self.link(node, l);
+                    if link.next.is_null() || pos + link.width >= target {
+                        break;
+                    }
+                    pos += link.width;
+                    node = link.next;
+                }
+            }
+            update[l] = node;
+            positions[l] = pos;
+        }
+        (update, positions)
+    }
+
+    // index番目のノード（index < len であること）
+    fn node_at(&self, index: usize) -> *mut SkipNode<T> {
+        let target = index + 1;
+        let mut links = &self.head[..];
+        let mut pos = 0;
+        for l in (0..self.level).rev() {
+            // 同じレベルで飛べるだけ飛ぶ
+            while !links[l].next.is_null() && pos + links[l].width <= target {
+                pos += links[l].width;
+                let next = links[l].next;
+                if pos == target {
+                    return next;
+                }
+                links = unsafe { &(*next).links };
+            }
+        }
+        unreachable!("index {} out of range (len {})", index, self.len)
+    }
+
+    // 全ノードを解放して空に戻す
+    fn cleanup(&mut self) {
+        let mut current = self.head[0].next;
+        while !current.is_null() {
+            unsafe {
+                let next = (*current).links[0].next;
+                // Boxに戻してDropさせる
+                let _ = Box::from_raw(current);
+                current = next;
+            }
+        }
+        self.head = [Link::NULL; MAX_LEVEL];
+        self.level = 0;
+        self.len = 0;
+    }
+}
+
+impl<T: Clone> SequenceBackend<T> for SkipList<T> {
+    type Iter<'a> = SkipListIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::with_seed(DEFAULT_LEVEL_SEED)
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Self::new()
+    }
+
+    fn append(&mut self, value: T) {
+        self.insert(self.len, value);
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        // 各レベルの最後のノードとその位置を覚えておき、そこへ後ろから繋いでいく
+        // （1つずつ append すると毎回上から降りるので O(N log N)。こちらは O(N)）
+        let (mut last, mut positions) = self.predecessors(self.len + 1);
+        for value in values {
+            let target = self.len + 1;
+            let height = self.random_level();
+            // 新しいレベルは head（null, 位置0）から始まる
+            self.level = self.level.max(height);
+            let node = Box::into_raw(Box::new(SkipNode {
+                value: value.clone(),
+                links: vec![Link::NULL; height].into_boxed_slice(),
+            }));
+            for l in 0..height {
+                let prev = unsafe { self.link(last[l], l) };
+                prev.next = node;
+                prev.width = target - positions[l];
+                last[l] = node;
+                positions[l] = target;
+            }
+            self.len += 1;
+        }
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        // 範囲外は末尾。新しいノードの位置は target
+        let target = index.min(self.len) + 1;
+        let height = self.random_level();
+        if height > self.level {
+            // 新しいレベルは head から始まる（predecessors で null = head になる）
+            self.level = height;
+        }
+        let (update, positions) = self.predecessors(target);
+        let node = Box::into_raw(Box::new(SkipNode {
+            value,
+            links: vec![Link::NULL; height].into_boxed_slice(),
+        }));
+        unsafe {
+            for l in 0..self.level {
+                let prev = self.link(update[l], l);
+                if l < height {
+                    // prev -> node -> 元の next に繋ぎ直し、width を分ける
+                    let next = *prev;
+                    prev.next = node;
+                    prev.width = target - positions[l];
+                    (*node).links[l] = Link {
+                        next: next.next,
+                        width: if next.next.is_null() { 0 } else { next.width + 1 - (target - positions[l]) },
+                    };
+                } else if !prev.next.is_null() {
+                    // 上を飛び越すリンクは1つ長くなる
+                    prev.width += 1;
+                }
+            }
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let target = index + 1;
+        let (update, _) = self.predecessors(target);
+        unsafe {
+            let node = self.link(update[0], 0).next;
+            for (l, &pred) in update.iter().enumerate().take(self.level) {
+                let prev = self.link(pred, l);
+                if prev.next == node {
+                    // node を飛ばして、その先へ繋ぐ
+                    let next = (*node).links[l];
+                    prev.next = next.next;
+                    prev.width += next.width;
+                    prev.width -= 1;
+                } else if !prev.next.is_null() {
+                    prev.width -= 1;
+                }
+            }
+            // 誰も使わなくなった上のレベルを畳む
+            while self.level > 0 && self.head[self.level - 1].next.is_null() {
+                self.level -= 1;
+            }
+            self.len -= 1;
+            let boxed = Box::from_raw(node);
+            Some(boxed.value)
+        }
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let node = self.node_at(index);
+        unsafe { Some((*node).value.clone()) }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> SkipListIter<'_, T> {
+        SkipListIter {
+            current: self.head[0].next,
+            _marker: PhantomData,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cleanup();
+    }
+}
+
+impl<T> Drop for SkipList<T> {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::diff::Oracle;
+    use crate::workload::{OpMix, PositionDist, Workload};
+
+    // 全レベルのリンクを辿り、width が位置の差と一致することを確かめてから、モデルと比べる
+    fn check(list: &SkipList, model: &[i32]) {
+        // レベル0で各ノードの位置（head が0）を数える
+        let mut position = HashMap::new();
+        let mut current = list.head[0].next;
+        while !current.is_null() {
+            position.insert(current, position.len() + 1);
+            current = unsafe { (*current).links[0].next };
+        }
+        assert_eq!(position.len(), list.len);
+
+        for l in 0..list.level {
+            assert!(!list.head[l].next.is_null(), "level {} is in use but empty", l);
+            let mut link = list.head[l];
+            let mut pos = 0;
+            while !link.next.is_null() {
+                let next_pos = position[&link.next];
+                assert_eq!(link.width, next_pos - pos, "width at level {} from position {}", l, pos);
+                // 高さが l 以下のノードがレベル l に繋がっていれば、ここで範囲外になる
+                link = unsafe { (*link.next).links[l] };
+                pos = next_pos;
+            }
+        }
+        assert!(list.head[list.level..].iter().all(|link| link.next.is_null()));
+
+        assert_eq!(list.len(), model.len());
+        assert!(list.iter().eq(model.iter().copied()));
+        for (i, v) in model.iter().enumerate() {
+            assert_eq!(list.get(i), Some(*v));
+        }
+        assert_eq!(list.get(model.len()), None);
+    }
+
+    #[test]
+    fn random_ops_match_the_oracle_and_keep_widths() {
+        let mix = OpMix { append: 1, insert: 4, remove: 3, get: 1, sum: 1 };
+        for dist in [PositionDist::Uniform, PositionDist::Head, PositionDist::Tail] {
+            let ops = Workload::new(2000, 0x0bad_5eed, mix, dist).with_prefill(10).generate();
+            let mut list = SkipList::with_seed(42);
+            let mut oracle = Oracle::default();
+            for (step, op) in ops.into_iter().enumerate() {
+                assert_eq!(op.apply(&mut list), op.apply(&mut oracle), "{:?} at step {}", op, step);
+                check(&list, &oracle.0);
+            }
+        }
+    }
+
+    #[test]
+    fn removing_the_last_element_keeps_widths_for_later_appends() {
+        let mut list = SkipList::with_seed(3);
+        let mut model: Vec<i32> = (0..64).collect();
+        list.extend_from_slice(&model);
+        check(&list, &model);
+        for round in 0..40 {
+            // 末尾を消すと、その手前のノードのリンクは null を指す（width は使わない）
+            assert_eq!(list.pop_back(), model.pop());
+            check(&list, &model);
+            if round % 3 == 0 {
+                list.append(100 + round);
+                model.push(100 + round);
+                check(&list, &model);
+            }
+        }
+        // 最後の1つまで消すとレベルも畳まれる
+        while let Some(v) = list.remove(list.len().saturating_sub(1)) {
+            assert_eq!(Some(v), model.pop());
+        }
+        assert_eq!(list.levels(), 0);
+        check(&list, &[]);
+        list.append(7);
+        check(&list, &[7]);
+    }
+
+    #[test]
+    fn bulk_extend_links_after_existing_nodes() {
+        let mut list = SkipList::new();
+        let mut model = vec![5, 6, 7];
+        for &v in &model {
+            list.append(v);
+        }
+        let more: Vec<i32> = (0..300).collect();
+        list.extend_from_slice(&more);
+        model.extend_from_slice(&more);
+        check(&list, &model);
+        list.insert(150, -1);
+        model.insert(150, -1);
+        check(&list, &model);
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_levels() {
+        let mut a = SkipList::with_seed(9);
+        let mut b = SkipList::with_seed(9);
+        let mut zero = SkipList::with_seed(0);
+        for i in 0..5000 {
+            a.insert(i / 2, i);
+            b.insert(i / 2, i);
+            zero.append(i);
+        }
+        assert_eq!(a.levels(), b.levels());
+        assert!(a.iter().eq(b.iter()));
+        // 期待値は log2 N ≈ 12。シード0でも乱数が止まらない
+        assert!((4..=32).contains(&zero.levels()), "levels {}", zero.levels());
+    }
+}
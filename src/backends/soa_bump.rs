@@ -0,0 +1,455 @@
+use rayon::prelude::*;
+
+use crate::parallel::{list_rank, scatter_by_rank};
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Bump Allocation, Structure of Arrays) Implementation
+// Impl: values / next / prev in separate arrays, linked by u32 index
+// ========================================================
+
+// BumpNode・UnsafeNode・WGSLの Node は value, next, prev を1つの構造体に並べる（AoS）。
+// ここでは同じ添字で3本の配列に分けて持つ（SoA）。ノードの確保は配列の末尾に
+// 1つ足すだけ（バンプ）で、removeしたスロットは BumpDll と同じくフリーリストで再利用する。
+// 先頭から順に append しただけのリストは「添字 = 位置」なので、iter() / sum() は values を
+// 先頭から読むだけになる（リンクを辿らない）。Zipperとの差のうち、どこまでがメモリ配置の
+// 差なのかを BumpDll と比べて測るための実装。
+
+// 添字の「無し」
+const NIL: u32 = u32::MAX;
+
+/// SoA配置のバンプリスト（要素は `BumpDll` と同じく `T: Copy`。外したスロットの値は放置する）
+pub struct SoaBumpDll<T = i32> {
+    values: Vec<T>,
+    next: Vec<u32>,
+    prev: Vec<u32>,
+    head: u32,
+    tail: u32,
+    len: usize,
+    // removeしたスロットを next で繋いだフリーリスト
+    free: u32,
+    // スロット 0..len がそのまま先頭からの順番になっているか（append と末尾の削除だけなら true）
+    in_order: bool,
+}
+
+/// 順番通りのリストなら values をそのまま読み、そうでなければ next を辿る
+pub struct SoaBumpDllIter<'a, T = i32> {
+    inner: IterInner<'a, T>,
+}
+
+enum IterInner<'a, T> {
+    InOrder(std::slice::Iter<'a, T>),
+    Linked { list: &'a SoaBumpDll<T>, current: u32 },
+}
+
+impl<T: Copy> Iterator for SoaBumpDllIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match &mut self.inner {
+            IterInner::InOrder(values) => values.next().copied(),
+            IterInner::Linked { list, current } => {
+                if *current == NIL {
+                    return None;
+                }
+                let index = *current as usize;
+                *current = list.next[index];
+                Some(list.values[index])
+            }
+        }
+    }
+
+    // sum() などは fold で回るので、順番通りならスライスの fold に任せる（ベクトル化される）
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        match self.inner {
+            IterInner::InOrder(values) => values.copied().fold(init, f),
+            IterInner::Linked { list, mut current } => {
+                let mut acc = init;
+                while current != NIL {
+                    let index = current as usize;
+                    acc = f(acc, list.values[index]);
+                    current = list.next[index];
+                }
+                acc
+            }
+        }
+    }
+}
+
+impl<T: Copy> SoaBumpDll<T> {
+    /// スロットが位置の順に並んでいて、`iter()` / `sum()` が values を直接読めるか
+    pub fn is_in_order(&self) -> bool {
+        self.in_order
+    }
+
+    // フリーリストから再利用するか、3本の配列の末尾に足す
+    fn alloc_slot(&mut self, value: T) -> u32 {
+        if self.free != NIL {
+            let slot = self.free;
+            self.free = self.next[slot as usize];
+            self.values[slot as usize] = value;
+            return slot;
+        }
+        let slot = u32::try_from(self.values.len())
+            .ok()
+            .filter(|&i| i != NIL)
+            .expect("SoaBumpDll is full (u32 indices)");
+        self.values.push(value);
+        self.next.push(NIL);
+        self.prev.push(NIL);
+        slot
+    }
+
+    // prev と next の間に新しいスロットを繋ぐ（どちらかが NIL なら端）
+    fn link_between(&mut self, prev: u32, next: u32, value: T) {
+        let slot = self.alloc_slot(value);
+        self.prev[slot as usize] = prev;
+        self.next[slot as usize] = next;
+        if prev != NIL {
+            self.next[prev as usize] = slot;
+        } else {
+            self.head = slot;
+        }
+        if next != NIL {
+            self.prev[next as usize] = slot;
+        } else {
+            self.tail = slot;
+        }
+        self.len += 1;
+    }
+
+    // スロットをリストから切り離してフリーリストへ戻し、値を返す
+    fn unlink(&mut self, slot: u32) -> T {
+        let prev = self.prev[slot as usize];
+        let next = self.next[slot as usize];
+        if prev != NIL {
+            self.next[prev as usize] = next;
+        } else {
+            self.head = next;
+        }
+        if next != NIL {
+            self.prev[next as usize] = prev;
+        } else {
+            self.tail = prev;
+        }
+        self.len -= 1;
+        let value = self.values[slot as usize];
+        if self.in_order && slot as usize == self.values.len() - 1 {
+            // 順番通りのリストの末尾なら、バンプを1つ巻き戻せば順番は崩れない
+            self.values.pop();
+            self.next.pop();
+            self.prev.pop();
+        } else {
+            self.next[slot as usize] = self.free;
+            self.free = slot;
+            self.in_order = false;
+        }
+        value
+    }
+
+    // index番目のスロットを head か tail の近い方から探す (範囲外なら NIL)
+    fn slot_at(&self, index: usize) -> u32 {
+        if index >= self.len {
+            return NIL;
+        }
+        if self.in_order {
+            return index as u32;
+        }
+        if index < self.len / 2 {
+            let mut current = self.head;
+            for _ in 0..index {
+                current = self.next[current as usize];
+            }
+            current
+        } else {
+            let mut current = self.tail;
+            for _ in index + 1..self.len {
+                current = self.prev[current as usize];
+            }
+            current
+        }
+    }
+}
+
+impl<T: Copy> SequenceBackend<T> for SoaBumpDll<T> {
+    type Iter<'a> = SoaBumpDllIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// 3本の配列をそれぞれ `capacity` 要素分確保する
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            next: Vec::with_capacity(capacity),
+            prev: Vec::with_capacity(capacity),
+            head: NIL,
+            tail: NIL,
+            len: 0,
+            free: NIL,
+            in_order: true,
+        }
+    }
+
+    fn append(&mut self, value: T) {
+        // フリーリストが空なら新しいスロットは末尾なので、順番は崩れない
+        // （in_order の間はフリーリストは常に空）
+        let tail = self.tail;
+        self.link_between(tail, NIL, value);
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        // フリーリストに残っているスロットを先に使う
+        let mut rest = values;
+        while self.free != NIL {
+            let Some((&value, after)) = rest.split_first() else {
+                return;
+            };
+            self.append(value);
+            rest = after;
+        }
+        if rest.is_empty() {
+            return;
+        }
+        // 残りは3本の配列の末尾へまとめて足し、隣のスロット同士を繋ぐ
+        // （in_order なら tail は最後のスロットなので、順番は崩れない）
+        let first = self.values.len();
+        // 最後のスロット end - 1 が NIL（u32::MAX）未満になること
+        let end = u32::try_from(first + rest.len()).expect("SoaBumpDll is full (u32 indices)");
+        let first = first as u32;
+        self.values.extend_from_slice(rest);
+        self.next.extend((first + 1..end).chain([NIL]));
+        self.prev.extend([self.tail].into_iter().chain(first..end - 1));
+        if self.tail != NIL {
+            self.next[self.tail as usize] = first;
+        } else {
+            self.head = first;
+        }
+        self.tail = end - 1;
+        self.len += rest.len();
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        if index >= self.len {
+            self.append(value);
+            return;
+        }
+        let next = self.slot_at(index);
+        let prev = self.prev[next as usize];
+        self.link_between(prev, next, value);
+        self.in_order = false;
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let slot = self.slot_at(index);
+        if slot == NIL {
+            return None;
+        }
+        Some(self.unlink(slot))
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        if self.head == NIL {
+            return None;
+        }
+        Some(self.unlink(self.head))
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        if self.tail == NIL {
+            return None;
+        }
+        Some(self.unlink(self.tail))
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        let slot = self.slot_at(index);
+        (slot != NIL).then(|| self.values[slot as usize])
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> SoaBumpDllIter<'_, T> {
+        let inner = if self.in_order {
+            // リンクを辿らず、values を先頭から流し読みする（Zipperと同じ読み方）
+            IterInner::InOrder(self.values.iter())
+        } else {
+            IterInner::Linked { list: self, current: self.head }
+        };
+        SoaBumpDllIter { inner }
+    }
+
+    fn clear(&mut self) {
+        // バンプを先頭まで巻き戻す（確保した容量は残す）
+        self.values.clear();
+        self.next.clear();
+        self.prev.clear();
+        self.head = NIL;
+        self.tail = NIL;
+        self.len = 0;
+        self.free = NIL;
+        self.in_order = true;
+    }
+}
+
+// --------------------------------------------------------
+// Parallel (rayon)
+// 順番通りなら values を分割するだけ。そうでなければ next 配列をリストランキングして並べ直す
+// --------------------------------------------------------
+
+impl<T: Copy + Send + Sync> SoaBumpDll<T> {
+    // リストに繋がっているスロット（フリーリストのスロットは false）
+    fn live_slots(&self) -> Vec<bool> {
+        let mut live = vec![true; self.values.len()];
+        let mut free = self.free;
+        while free != NIL {
+            live[free as usize] = false;
+            free = self.next[free as usize];
+        }
+        live
+    }
+
+    /// 先頭から順の `Vec`（`to_vec()` の並列版）
+    pub fn par_to_vec(&self) -> Vec<T> {
+        if self.in_order {
+            return self.values.par_iter().copied().collect();
+        }
+        let live = self.live_slots();
+        // フリーリストの鎖は切っておく（ランクは求めるが並べ直しでは使わない）
+        let succ: Vec<u32> =
+            self.next.par_iter().zip(&live).map(|(&next, &live)| if live { next } else { NIL }).collect();
+        let rank = list_rank(&succ);
+        // 先頭から繋がっている len 個のスロットの rank は len-1..=0 を1回ずつ取る
+        unsafe { scatter_by_rank(&self.values, &rank, &live, self.len) }
+    }
+
+    /// `sum_i64()` の並列版（合計は順番によらないので、生きているスロットを足すだけ）
+    pub fn par_sum_i64(&self) -> i64
+    where
+        T: Into<i64>,
+    {
+        if self.in_order {
+            return self.values.par_iter().map(|&value| value.into()).sum();
+        }
+        let live = self.live_slots();
+        self.values.par_iter().zip(&live).filter(|(_, &live)| live).map(|(&value, _)| value.into()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::Oracle;
+    use crate::workload::{OpMix, PositionDist, Workload};
+
+    // リンク・フリーリスト・in_order の前提を確かめてから、並列版の走査をモデルと比べる
+    fn check(list: &SoaBumpDll, model: &[i32]) {
+        let mut prev = NIL;
+        let mut current = list.head;
+        let mut position = 0;
+        while current != NIL {
+            assert_eq!(list.prev[current as usize], prev);
+            if list.in_order {
+                assert_eq!(current as usize, position, "in_order list with slot != position");
+            }
+            prev = current;
+            current = list.next[current as usize];
+            position += 1;
+        }
+        assert_eq!(list.tail, prev);
+        assert_eq!(position, list.len);
+
+        let live = list.live_slots().iter().filter(|&&live| live).count();
+        assert_eq!(live, list.len, "free list and linked slots overlap or leak");
+        if list.in_order {
+            assert_eq!(list.free, NIL);
+            assert_eq!(list.values.len(), list.len);
+        }
+
+        assert_eq!(list.par_to_vec(), model);
+        assert_eq!(list.par_sum_i64(), list.sum_i64());
+    }
+
+    // 末尾の操作は順番通りのまま、途中の削除でフリーリストへ移る
+    #[test]
+    fn random_ops_match_the_oracle() {
+        let mix = OpMix { append: 3, insert: 2, remove: 3, get: 1, sum: 1 };
+        for dist in [PositionDist::Tail, PositionDist::Uniform, PositionDist::Head] {
+            let ops = Workload::new(1500, 0x7f4a_7c15, mix, dist).with_prefill(10).generate();
+            let mut list = SoaBumpDll::new();
+            let mut oracle = Oracle::default();
+            for (step, op) in ops.into_iter().enumerate() {
+                assert_eq!(op.apply(&mut list), op.apply(&mut oracle), "{:?} at step {}", op, step);
+                check(&list, &oracle.0);
+            }
+        }
+    }
+
+    #[test]
+    fn appends_and_tail_removals_stay_in_order() {
+        let mut list = SoaBumpDll::new();
+        list.extend_from_slice(&[0, 1, 2, 3]);
+        list.append(4);
+        assert!(list.is_in_order());
+        // 末尾の削除はバンプを巻き戻すだけ
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.remove(3), Some(3));
+        assert!(list.is_in_order());
+        assert_eq!(list.values.len(), 3);
+        check(&list, &[0, 1, 2]);
+    }
+
+    #[test]
+    fn free_slots_are_reused_after_in_order_drops() {
+        let mut list = SoaBumpDll::new();
+        let mut model: Vec<i32> = (0..8).collect();
+        list.extend_from_slice(&model);
+
+        // 真ん中を消すと順番が崩れ、スロット2がフリーリストへ
+        assert_eq!(list.remove(2), Some(model.remove(2)));
+        assert!(!list.is_in_order());
+        assert_eq!(list.free, 2);
+        check(&list, &model);
+
+        // 末尾の削除も巻き戻さずフリーリストへ（2 の前に積まれる）
+        assert_eq!(list.pop_back(), model.pop());
+        assert_eq!((list.free, list.values.len()), (7, 8));
+
+        // append はフリーリストの新しい方から使い、配列は伸びない
+        list.append(100);
+        model.push(100);
+        list.insert(0, 200);
+        model.insert(0, 200);
+        assert_eq!((list.free, list.values.len()), (NIL, 8));
+        assert_eq!((list.head, list.tail), (2, 7));
+        check(&list, &model);
+
+        // フリーリストを使い切った後は末尾にまとめて足す
+        list.remove(1);
+        model.remove(1);
+        let more = [300, 301, 302];
+        list.extend_from_slice(&more);
+        model.extend_from_slice(&more);
+        assert_eq!((list.free, list.values.len()), (NIL, 10));
+        assert!(!list.is_in_order());
+        check(&list, &model);
+
+        // clear で順番通りに戻り、スロット0から使い直す
+        list.clear();
+        assert!(list.is_in_order());
+        list.extend_from_slice(&[7, 8]);
+        assert_eq!((list.head, list.tail), (0, 1));
+        check(&list, &[7, 8]);
+    }
+}
@@ -0,0 +1,479 @@
+use std::ops::{Add, Range, Sub};
+
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Implicit Treap) Implementation
+// Impl: Box tree keyed by position (subtree size), random priorities
+// ========================================================
+
+// キーを持たず「左部分木のサイズ = 自分の位置」として扱う平衡二分木（implicit treap / rope）。
+// insert / remove は split と merge で木を組み替え、どれも期待 O(log N)。
+// 部分木ごとの要素数と合計を持つので、get と range_sum も O(log N)。
+
+/// 部分木の合計を持つための要素型の指定
+/// （i32の合計はi64で持つ、のように、溢れない型で足す）
+pub trait Measured: Clone {
+    type Measure: Copy + Default + Add<Output = Self::Measure> + Sub<Output = Self::Measure>;
+
+    fn measure(&self) -> Self::Measure;
+}
+
+impl Measured for i32 {
+    type Measure = i64;
+
+    fn measure(&self) -> i64 {
+        i64::from(*self)
+    }
+}
+
+impl Measured for i64 {
+    type Measure = i128;
+
+    fn measure(&self) -> i128 {
+        i128::from(*self)
+    }
+}
+
+impl Measured for f64 {
+    type Measure = f64;
+
+    fn measure(&self) -> f64 {
+        *self
+    }
+}
+
+type Link<T> = Option<Box<TreapNode<T>>>;
+
+struct TreapNode<T: Measured> {
+    value: T,
+    priority: u32,
+    size: usize,       // 部分木の要素数
+    total: T::Measure, // 部分木の合計
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T: Measured> TreapNode<T> {
+    // 子が変わった後に size と total を計算し直す
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+        self.total = total(&self.left) + self.value.measure() + total(&self.right);
+    }
+}
+
+fn size<T: Measured>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+fn total<T: Measured>(link: &Link<T>) -> T::Measure {
+    link.as_ref().map_or_else(T::Measure::default, |node| node.total)
+}
+
+// 先頭 k 要素とそれ以外に分ける
+fn split<T: Measured>(link: Link<T>, k: usize) -> (Link<T>, Link<T>) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    let left_size = size(&node.left);
+    if k <= left_size {
+        let (l, r) = split(node.left.take(), k);
+        node.left = r;
+        node.update();
+        (l, Some(node))
+    } else {
+        let (l, r) = split(node.right.take(), k - left_size - 1);
+        node.right = l;
+        node.update();
+        (Some(node), r)
+    }
+}
+
+// a の後ろに b を繋ぐ（優先度の高い方が根になる）
+fn merge<T: Measured>(a: Link<T>, b: Link<T>) -> Link<T> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority > b.priority {
+                a.right = merge(a.right.take(), Some(b));
+                a.update();
+                Some(a)
+            } else {
+                b.left = merge(Some(a), b.left.take());
+                b.update();
+                Some(b)
+            }
+        }
+    }
+}
+
+// 位置の順に並んだノードから、右の枝をスタックにして O(N) で木を組む（Cartesian tree）
+fn build<T: Measured>(nodes: impl Iterator<Item = Box<TreapNode<T>>>) -> Link<T> {
+    // 根から右端までの枝。各ノードの右の子はまだ繋いでいない（スタックの1つ上がそれ）
+    let mut spine: Vec<Box<TreapNode<T>>> = Vec::new();
+    for mut node in nodes {
+        // 優先度が node 以下の枝は node の左の子になる（merge と同じく、同じなら後ろが上）
+        let mut left: Link<T> = None;
+        while spine.last().is_some_and(|top| top.priority <= node.priority) {
+            let mut top = spine.pop().expect("checked by is_some_and");
+            top.right = left;
+            top.update();
+            left = Some(top);
+        }
+        node.left = left;
+        node.update();
+        spine.push(node);
+    }
+    // 枝を下から畳んで根まで繋ぐ
+    let mut built: Link<T> = None;
+    while let Some(mut top) = spine.pop() {
+        top.right = built;
+        top.update();
+        built = Some(top);
+    }
+    built
+}
+
+// 優先度が new より低い最初の部分木を split して、new をそこの根にする
+// （split + merge 2回より辿る回数が少ない）
+fn insert_at<T: Measured>(link: Link<T>, index: usize, mut new: Box<TreapNode<T>>) -> Box<TreapNode<T>> {
+    match link {
+        None => new,
+        Some(node) if new.priority > node.priority => {
+            let (l, r) = split(Some(node), index);
+            new.left = l;
+            new.right = r;
+            new.update();
+            new
+        }
+        Some(mut node) => {
+            let left_size = size(&node.left);
+            if index <= left_size {
+                node.left = Some(insert_at(node.left.take(), index, new));
+            } else {
+                node.right = Some(insert_at(node.right.take(), index - left_size - 1, new));
+            }
+            node.update();
+            node
+        }
+    }
+}
+
+// index番目のノードを外し、その左右の部分木を merge して繋ぎ直す (index < size であること)
+fn remove_at<T: Measured>(link: &mut Link<T>, index: usize) -> T {
+    let node = link.as_mut().expect("index out of range");
+    let left_size = size(&node.left);
+    if index < left_size {
+        let value = remove_at(&mut node.left, index);
+        node.update();
+        value
+    } else if index > left_size {
+        let value = remove_at(&mut node.right, index - left_size - 1);
+        node.update();
+        value
+    } else {
+        let mut node = link.take().expect("index out of range");
+        *link = merge(node.left.take(), node.right.take());
+        node.value
+    }
+}
+
+pub struct TreapList<T: Measured = i32> {
+    root: Link<T>,
+    rng: u64, // 優先度用の xorshift64（同じシードなら同じ形の木になる）
+}
+
+pub struct TreapIter<'a, T: Measured = i32> {
+    // 左の枝を積んでおき、popした順が中間順（= 先頭から）になる
+    stack: Vec<&'a TreapNode<T>>,
+}
+
+impl<'a, T: Measured> TreapIter<'a, T> {
+    fn push_left(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<T: Measured> Iterator for TreapIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.stack.pop()?;
+        self.push_left(&node.right);
+        Some(node.value.clone())
+    }
+}
+
+impl<T: Measured> TreapList<T> {
+    /// 優先度の乱数列を `seed` で決めたリスト
+    pub fn with_seed(seed: u64) -> Self {
+        // xorshiftは0から抜け出せないので避ける
+        Self { root: None, rng: seed.max(1) }
+    }
+
+    fn next_priority(&mut self) -> u32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 32) as u32
+    }
+
+    /// 先頭 `k` 要素の合計（`k > len` は全体）
+    pub fn prefix_sum(&self, k: usize) -> T::Measure {
+        let mut acc = T::Measure::default();
+        let mut k = k;
+        let mut link = &self.root;
+        while let Some(node) = link {
+            let left_size = size(&node.left);
+            if k <= left_size {
+                link = &node.left;
+            } else {
+                acc = acc + total(&node.left) + node.value.measure();
+                k -= left_size + 1;
+                link = &node.right;
+            }
+        }
+        acc
+    }
+
+    /// `range` の要素の合計（O(log N)）。範囲外の部分は無視する
+    pub fn range_sum(&self, range: Range<usize>) -> T::Measure {
+        if range.start >= range.end {
+            return T::Measure::default();
+        }
+        self.prefix_sum(range.end) - self.prefix_sum(range.start)
+    }
+
+    /// 全体の合計（根に持っている値なので O(1)）
+    pub fn total(&self) -> T::Measure {
+        total(&self.root)
+    }
+
+    /// 木の高さ（平衡の確認用）
+    pub fn height(&self) -> usize {
+        fn height<T: Measured>(link: &Link<T>) -> usize {
+            link.as_ref().map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+        }
+        height(&self.root)
+    }
+}
+
+impl<T: Measured> SequenceBackend<T> for TreapList<T> {
+    type Iter<'a> = TreapIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::with_seed(0x9e37_79b9_7f4a_7c15)
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Self::new()
+    }
+
+    fn append(&mut self, value: T) {
+        let len = self.len();
+        self.insert(len, value);
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        // 1つずつ insert_at で根から辿る（O(N log N)）代わりに、まとめて木にしてから1回だけ merge
+        let built = build(values.iter().map(|value| {
+            Box::new(TreapNode {
+                total: value.measure(),
+                value: value.clone(),
+                priority: self.next_priority(),
+                size: 1,
+                left: None,
+                right: None,
+            })
+        }));
+        self.root = merge(self.root.take(), built);
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        let node = Box::new(TreapNode {
+            total: value.measure(),
+            value,
+            priority: self.next_priority(),
+            size: 1,
+            left: None,
+            right: None,
+        });
+        // 範囲外は末尾
+        let index = index.min(self.len());
+        self.root = Some(insert_at(self.root.take(), index, node));
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(remove_at(&mut self.root, index))
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len() {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        let mut index = index;
+        let mut link = &self.root;
+        while let Some(node) = link {
+            let left_size = size(&node.left);
+            if index < left_size {
+                link = &node.left;
+            } else if index == left_size {
+                return Some(node.value.clone());
+            } else {
+                index -= left_size + 1;
+                link = &node.right;
+            }
+        }
+        None
+    }
+
+    fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    fn iter(&self) -> TreapIter<'_, T> {
+        let mut iter = TreapIter { stack: Vec::new() };
+        iter.push_left(&self.root);
+        iter
+    }
+
+    fn clear(&mut self) {
+        self.root = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+    use crate::workload::{run_ops, OpMix, PositionDist, Workload};
+    use crate::BackendKind;
+
+    // size / total / 優先度のヒープ条件を全ノードで確かめる
+    fn check_node(link: &Link<i32>) -> (usize, i64) {
+        let Some(node) = link else {
+            return (0, 0);
+        };
+        for child in [&node.left, &node.right].into_iter().flatten() {
+            assert!(child.priority <= node.priority, "heap order broken");
+        }
+        let (left_size, left_total) = check_node(&node.left);
+        let (right_size, right_total) = check_node(&node.right);
+        assert_eq!(node.size, left_size + 1 + right_size);
+        assert_eq!(node.total, left_total + i64::from(node.value) + right_total);
+        (node.size, node.total)
+    }
+
+    fn check(list: &TreapList, model: &[i32]) {
+        check_node(&list.root);
+        assert_eq!(list.len(), model.len());
+        assert!(list.iter().eq(model.iter().copied()));
+        assert_eq!(list.total(), model.iter().map(|&v| i64::from(v)).sum::<i64>());
+        assert_eq!(list.get(model.len()), None);
+    }
+
+    fn model_sum(model: &[i32], start: usize, end: usize) -> i64 {
+        let end = end.min(model.len());
+        let start = start.min(end);
+        model[start..end].iter().map(|&v| i64::from(v)).sum()
+    }
+
+    #[test]
+    fn random_ops_match_the_oracle() {
+        let mix = OpMix { append: 1, insert: 4, remove: 2, get: 1, sum: 1 };
+        for dist in [PositionDist::Uniform, PositionDist::FrontBiased, PositionDist::Tail] {
+            let ops = Workload::new(3000, 0x1234_5677, mix, dist).with_prefill(20).generate();
+            if let Err(d) = diff::check_backend(BackendKind::Treap, &ops) {
+                panic!("{}", d);
+            }
+
+            // 順序はオラクルが見ているので、ここでは木の不変条件と区間和
+            let mut list = TreapList::new();
+            run_ops(&mut list, ops);
+            let model: Vec<i32> = list.iter().collect();
+            check(&list, &model);
+            for a in (0..=model.len() + 1).step_by(7) {
+                assert_eq!(list.prefix_sum(a), model_sum(&model, 0, a));
+                assert_eq!(list.range_sum(a..a + 13), model_sum(&model, a, a + 13));
+            }
+        }
+    }
+
+    #[test]
+    fn range_and_prefix_sums_clip_to_the_bounds() {
+        let empty = TreapList::<i32>::new();
+        assert_eq!((empty.prefix_sum(0), empty.prefix_sum(5), empty.range_sum(0..5), empty.total()), (0, 0, 0, 0));
+
+        let list: TreapList = TreapList::from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(list.prefix_sum(0), 0);
+        assert_eq!(list.prefix_sum(1), 1);
+        assert_eq!(list.prefix_sum(5), 15);
+        assert_eq!(list.prefix_sum(6), 15);
+        assert_eq!(list.prefix_sum(usize::MAX), 15);
+
+        assert_eq!(list.range_sum(0..5), 15);
+        assert_eq!(list.range_sum(1..4), 9);
+        assert_eq!(list.range_sum(4..5), 5);
+        assert_eq!(list.range_sum(2..2), 0);
+        // 範囲外の部分は無視、逆順は空
+        assert_eq!(list.range_sum(3..100), 9);
+        assert_eq!(list.range_sum(5..9), 0);
+        assert_eq!(list.range_sum(0..usize::MAX), 15);
+        let (start, end) = (4, 1);
+        assert_eq!(list.range_sum(start..end), 0);
+
+        // i32 の合計は i64 で溢れない
+        let big: TreapList = TreapList::from_slice(&[i32::MAX; 4]);
+        assert_eq!(big.range_sum(1..4), 3 * i64::from(i32::MAX));
+    }
+
+    #[test]
+    fn bulk_build_keeps_the_invariants() {
+        let mut list = TreapList::new();
+        list.insert(0, -1);
+        let values: Vec<i32> = (0..500).collect();
+        list.extend_from_slice(&values);
+        let mut model = vec![-1];
+        model.extend_from_slice(&values);
+        check(&list, &model);
+        list.extend_from_slice(&[]);
+        check(&list, &model);
+    }
+
+    #[test]
+    fn same_seed_builds_the_same_tree_and_stays_shallow() {
+        let mut a = TreapList::with_seed(7);
+        let mut b = TreapList::with_seed(7);
+        for i in 0..10_000 {
+            a.append(i);
+            b.append(i);
+        }
+        assert_eq!(a.height(), b.height());
+        // 期待値は 2 ln N ≈ 18。大きく外れたら平衡が壊れている
+        assert!(a.height() < 60, "height {}", a.height());
+        // シード0でも優先度が0に張り付かない
+        let mut zero: TreapList = TreapList::with_seed(0);
+        zero.extend_from_slice(&(0..1000).collect::<Vec<i32>>());
+        assert!(zero.height() < 60, "height {}", zero.height());
+    }
+}
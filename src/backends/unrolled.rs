@@ -0,0 +1,473 @@
+use std::iter::Sum;
+use std::marker::PhantomData;
+
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Unrolled Linked List) Implementation
+// Impl: Raw-pointer DLL of fixed-capacity blocks (Vec<T> per node)
+// ========================================================
+
+// UnsafeDll（1ノード1要素）と ZipperList（全体で1本の配列）の中間。
+// ノードごとに block_size 個までの配列を持ち、満杯なら半分に分割し、
+// 削除で半分を切ったら次のブロックと併合する。
+// 位置を探すのはブロック単位（O(N / block_size)）、ブロック内はVecのシフト（O(block_size)）。
+
+/// ブロックサイズを指定しないときの値（i32なら1KiB。N=30,000のRandom Insertで最速だった付近）
+pub const DEFAULT_BLOCK_SIZE: usize = 256;
+
+struct Block<T> {
+    items: Vec<T>, // capacity == block_size（再確保しない）
+    next: *mut Block<T>,
+    prev: *mut Block<T>,
+}
+
+pub struct UnrolledList<T = i32> {
+    head: *mut Block<T>,
+    tail: *mut Block<T>,
+    len: usize,
+    block_size: usize,
+}
+
+pub struct UnrolledIter<'a, T = i32> {
+    block: *mut Block<T>,
+    offset: usize,
+    _marker: PhantomData<&'a UnrolledList<T>>,
+}
+
+impl<T: Clone> Iterator for UnrolledIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while !self.block.is_null() {
+                let items = &(*self.block).items;
+                if self.offset < items.len() {
+                    self.offset += 1;
+                    return Some(items[self.offset - 1].clone());
+                }
+                self.block = (*self.block).next;
+                self.offset = 0;
+            }
+        }
+        None
+    }
+}
+
+impl<T> UnrolledList<T> {
+    /// 1ブロックあたり最大 `block_size` 要素のリスト（`block_size >= 2`）
+    pub fn with_block_size(block_size: usize) -> Self {
+        assert!(block_size >= 2, "block_size must be at least 2 (got {})", block_size);
+        Self {
+            head: std::ptr::null_mut(),
+            tail: std::ptr::null_mut(),
+            len: 0,
+            block_size,
+        }
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// 今あるブロックの数
+    pub fn blocks(&self) -> usize {
+        self.block_slices().count()
+    }
+
+    // 先頭から順に、各ブロックの中身
+    fn block_slices(&self) -> impl Iterator<Item = &[T]> + '_ {
+        let mut current = self.head;
+        std::iter::from_fn(move || {
+            let block = unsafe { current.as_ref()? };
+            current = block.next;
+            Some(block.items.as_slice())
+        })
+    }
+
+    // 全ブロックを解放して空に戻す
+    fn cleanup(&mut self) {
+        unsafe {
+            let mut current = self.head;
+            while !current.is_null() {
+                let next = (*current).next;
+                // Boxに戻してDropさせる（中のVecも解放される）
+                let _ = Box::from_raw(current);
+                current = next;
+            }
+        }
+        self.head = std::ptr::null_mut();
+        self.tail = std::ptr::null_mut();
+        self.len = 0;
+    }
+
+    // index番目の要素がある (ブロック, ブロック内の位置) を探す
+    // head・tail の近い方からブロック単位で飛ばす (index < len であること)
+    fn locate(&self, index: usize) -> (*mut Block<T>, usize) {
+        unsafe {
+            if index < self.len / 2 {
+                let mut block = self.head;
+                let mut offset = index;
+                while offset >= (*block).items.len() {
+                    offset -= (*block).items.len();
+                    block = (*block).next;
+                }
+                (block, offset)
+            } else {
+                // 後ろから数えた位置で探す
+                let mut block = self.tail;
+                let mut from_back = self.len - 1 - index;
+                while from_back >= (*block).items.len() {
+                    from_back -= (*block).items.len();
+                    block = (*block).prev;
+                }
+                (block, (*block).items.len() - 1 - from_back)
+            }
+        }
+    }
+
+    // prev の後ろに空のブロックを繋ぐ（prev が null なら先頭）
+    unsafe fn new_block_after(&mut self, prev: *mut Block<T>) -> *mut Block<T> {
+        let next = if prev.is_null() { self.head } else { (*prev).next };
+        let block = Box::into_raw(Box::new(Block {
+            items: Vec::with_capacity(self.block_size),
+            next,
+            prev,
+        }));
+        if !prev.is_null() {
+            (*prev).next = block;
+        } else {
+            self.head = block;
+        }
+        if !next.is_null() {
+            (*next).prev = block;
+        } else {
+            self.tail = block;
+        }
+        block
+    }
+
+    // ブロックを切り離して解放する（中身は呼び出し側で移しておくこと）
+    unsafe fn unlink_block(&mut self, block: *mut Block<T>) {
+        let prev = (*block).prev;
+        let next = (*block).next;
+        if !prev.is_null() {
+            (*prev).next = next;
+        } else {
+            self.head = next;
+        }
+        if !next.is_null() {
+            (*next).prev = prev;
+        } else {
+            self.tail = prev;
+        }
+        let _ = Box::from_raw(block);
+    }
+
+    // 満杯のブロックを半分に分け、後ろ半分を新しいブロックへ移す
+    unsafe fn split(&mut self, block: *mut Block<T>) -> *mut Block<T> {
+        let half = (*block).items.len() / 2;
+        let right = self.new_block_after(block);
+        (*right).items.extend((*block).items.drain(half..));
+        right
+    }
+
+    // 削除で半分を切ったブロックを、次のブロックと併合する（入り切る場合のみ）
+    unsafe fn merge_if_sparse(&mut self, block: *mut Block<T>) {
+        if (*block).items.is_empty() {
+            self.unlink_block(block);
+            return;
+        }
+        let next = (*block).next;
+        if (*block).items.len() >= self.block_size / 2 || next.is_null() {
+            return;
+        }
+        if (*block).items.len() + (*next).items.len() <= self.block_size {
+            (*block).items.append(&mut (*next).items);
+            self.unlink_block(next);
+        }
+    }
+}
+
+impl<T: Clone> SequenceBackend<T> for UnrolledList<T> {
+    type Iter<'a> = UnrolledIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::with_block_size(DEFAULT_BLOCK_SIZE)
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Self::new()
+    }
+
+    fn append(&mut self, value: T) {
+        unsafe {
+            let mut block = self.tail;
+            if block.is_null() || (*block).items.len() == self.block_size {
+                // 末尾ブロックが満杯なら分割せずに新しいブロックを足す（末尾追加が続く前提）
+                block = self.new_block_after(block);
+            }
+            (*block).items.push(value);
+        }
+        self.len += 1;
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        let mut rest = values;
+        unsafe {
+            // 末尾ブロックの空きを埋めてから、残りを block_size 個ずつ新しいブロックへ
+            let tail = self.tail;
+            if !tail.is_null() {
+                let items = &mut (*tail).items;
+                let room = self.block_size - items.len();
+                let (fill, after) = rest.split_at(room.min(rest.len()));
+                items.extend_from_slice(fill);
+                rest = after;
+            }
+            for chunk in rest.chunks(self.block_size) {
+                let block = self.new_block_after(self.tail);
+                (*block).items.extend_from_slice(chunk);
+            }
+        }
+        self.len += values.len();
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        if index >= self.len {
+            self.append(value);
+            return;
+        }
+        unsafe {
+            let (mut block, mut offset) = self.locate(index);
+            if (*block).items.len() == self.block_size {
+                let right = self.split(block);
+                let left_len = (*block).items.len();
+                if offset > left_len {
+                    block = right;
+                    offset -= left_len;
+                }
+            }
+            // ブロック内のシフトは高々 block_size 要素
+            (*block).items.insert(offset, value);
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        unsafe {
+            let (block, offset) = self.locate(index);
+            let value = (*block).items.remove(offset);
+            self.len -= 1;
+            self.merge_if_sparse(block);
+            Some(value)
+        }
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let (block, offset) = self.locate(index);
+        let items = unsafe { &(*block).items };
+        Some(items[offset].clone())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        // ブロックごとに連続した配列を足してから、ブロックの合計を足す
+        self.block_slices().map(|items| items.iter().cloned().sum::<T>()).sum()
+    }
+
+    fn iter(&self) -> UnrolledIter<'_, T> {
+        UnrolledIter {
+            block: self.head,
+            offset: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cleanup();
+    }
+}
+
+impl<T> Drop for UnrolledList<T> {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+    use crate::workload::{OpMix, PositionDist, Workload};
+
+    // 空のブロックが残っていないこと、prev/next と tail、要素数の合計
+    fn check_chain(list: &UnrolledList) {
+        unsafe {
+            let mut prev: *mut Block<i32> = std::ptr::null_mut();
+            let mut current = list.head;
+            let mut total = 0;
+            while !current.is_null() {
+                let items = &(*current).items;
+                assert!(!items.is_empty(), "empty block left in the chain");
+                assert!(items.len() <= list.block_size);
+                assert_eq!((*current).prev, prev);
+                total += items.len();
+                prev = current;
+                current = (*current).next;
+            }
+            assert_eq!(list.tail, prev);
+            assert_eq!(total, list.len());
+        }
+    }
+
+    fn check(list: &UnrolledList, model: &[i32]) {
+        check_chain(list);
+        assert!(list.iter().eq(model.iter().copied()));
+        assert_eq!(list.get(model.len()), None);
+    }
+
+    fn block_lens(list: &UnrolledList) -> Vec<usize> {
+        list.block_slices().map(|items| items.len()).collect()
+    }
+
+    #[test]
+    fn random_ops_match_the_oracle_for_small_block_sizes() {
+        let mix = OpMix { append: 1, insert: 4, remove: 3, get: 1, sum: 1 };
+        for block_size in [2, 3, 4, 16] {
+            let new_list = || -> Box<dyn crate::sequence::DynSequence> {
+                Box::new(UnrolledList::<i32>::with_block_size(block_size))
+            };
+            for dist in [PositionDist::Uniform, PositionDist::Head, PositionDist::Tail] {
+                let ops = Workload::new(1500, 0x9e37_79b9, mix, dist).with_prefill(10).generate();
+                diff::assert_matches_oracle(&format!("block_size {}", block_size), &new_list, &ops);
+
+                // 中身はオラクルが見ているので、ここではブロックの繋がりだけ
+                let mut list = UnrolledList::with_block_size(block_size);
+                for op in ops {
+                    op.apply(&mut list);
+                    check_chain(&list);
+                }
+                while list.pop_back().is_some() {}
+                check_chain(&list);
+                assert_eq!(list.blocks(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn block_size_2_splits_a_full_block_in_half() {
+        // 満杯なので [1] [2] に分け、位置1は左のブロックの末尾に入る
+        let mut list = UnrolledList::with_block_size(2);
+        list.extend_from_slice(&[1, 2]);
+        list.insert(1, 9);
+        assert_eq!(block_lens(&list), [2, 1]);
+        check(&list, &[1, 9, 2]);
+        // 右のブロックに入る場合
+        list.insert(2, 8);
+        assert_eq!(block_lens(&list), [2, 2]);
+        list.insert(3, 7);
+        assert_eq!(block_lens(&list), [2, 2, 1]);
+        check(&list, &[1, 9, 8, 7, 2]);
+        // 先頭への挿入
+        list.insert(0, 0);
+        assert_eq!(block_lens(&list), [2, 1, 2, 1]);
+        check(&list, &[0, 1, 9, 8, 7, 2]);
+
+        list.clear();
+        check(&list, &[]);
+    }
+
+    #[test]
+    fn block_size_2_unlinks_emptied_blocks_at_head_middle_and_tail() {
+        let mut list = UnrolledList::with_block_size(2);
+        list.extend_from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(block_lens(&list), [2, 2, 1]);
+
+        // 末尾ブロックが空になる
+        assert_eq!(list.remove(4), Some(5));
+        assert_eq!(block_lens(&list), [2, 2]);
+        check(&list, &[1, 2, 3, 4]);
+
+        // 半分 (1) 未満にはならないので、空になるまで併合はしない
+        assert_eq!(list.remove(2), Some(3));
+        assert_eq!(block_lens(&list), [2, 1]);
+        assert_eq!(list.remove(0), Some(1));
+        assert_eq!(block_lens(&list), [1, 1]);
+
+        // 先頭ブロックが空になる
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(block_lens(&list), [1]);
+        check(&list, &[4]);
+
+        list.extend_from_slice(&[6, 7, 8]);
+        assert_eq!(block_lens(&list), [2, 2]);
+        list.insert(2, 10);
+        assert_eq!(block_lens(&list), [2, 2, 1]);
+        // 真ん中のブロックが空になる
+        assert_eq!(list.remove(2), Some(10));
+        assert_eq!(list.remove(2), Some(7));
+        assert_eq!(block_lens(&list), [2, 1]);
+        check(&list, &[4, 6, 8]);
+    }
+
+    #[test]
+    fn sparse_blocks_merge_with_the_next_when_they_fit() {
+        let mut list = UnrolledList::with_block_size(4);
+        list.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(block_lens(&list), [4, 4]);
+        list.remove(0);
+        list.remove(0);
+        // 2個では半分未満にならない
+        assert_eq!(block_lens(&list), [2, 4]);
+        assert_eq!(list.remove(4), Some(7));
+        assert_eq!(block_lens(&list), [2, 3]);
+        // 1個になったら次の3個と併合して4個
+        list.remove(0);
+        assert_eq!(block_lens(&list), [4]);
+        check(&list, &[4, 5, 6, 8]);
+    }
+
+    #[test]
+    fn extend_fills_the_tail_block_first() {
+        let mut list = UnrolledList::with_block_size(3);
+        list.append(0);
+        list.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(block_lens(&list), [3, 3, 1]);
+        check(&list, &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.sum(), 21);
+    }
+
+    #[test]
+    #[should_panic(expected = "block_size must be at least 2")]
+    fn block_size_below_2_is_rejected() {
+        let _ = UnrolledList::<i32>::with_block_size(1);
+    }
+}
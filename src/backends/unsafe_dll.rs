@@ -0,0 +1,561 @@
+use std::marker::PhantomData;
+
+use crate::SequenceBackend;
+
+// ========================================================
+// Rust (Unsafe) Implementation
+// Impl: Raw Pointers (*mut T) without Rc/RefCell
+// ========================================================
+
+struct UnsafeNode<T> {
+    value: T,
+    next: *mut UnsafeNode<T>,
+    prev: *mut UnsafeNode<T>,
+}
+
+pub struct UnsafeDll<T = i32> {
+    head: *mut UnsafeNode<T>,
+    tail: *mut UnsafeNode<T>,
+    len: usize,
+    // 最後にカーソルがいた位置。null は末尾の外（ghost）で、そのとき cursor_index == len
+    cursor: *mut UnsafeNode<T>,
+    cursor_index: usize,
+}
+
+pub struct UnsafeDllIter<'a, T = i32> {
+    current: *mut UnsafeNode<T>,
+    _marker: PhantomData<&'a UnsafeDll<T>>,
+}
+
+impl<T: Clone> Iterator for UnsafeDllIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.current.is_null() {
+            return None;
+        }
+        unsafe {
+            let value = (*self.current).value.clone();
+            self.current = (*self.current).next;
+            Some(value)
+        }
+    }
+}
+
+/// 位置を覚えておくカーソル（`std::collections::linked_list::CursorMut` と同じ考え方）。
+///
+/// 要素を指しているか、末尾と先頭の間にある「ghost」（`index() == None`）を指す。
+/// 位置はリスト側に保存されるので、カーソルを作り直しても前回の位置から再開する。
+pub struct UnsafeDllCursorMut<'a, T = i32> {
+    list: &'a mut UnsafeDll<T>,
+}
+
+impl<T> UnsafeDll<T> {
+    /// 要素を持たない空のリスト
+    pub const fn new_empty() -> Self {
+        Self {
+            head: std::ptr::null_mut(),
+            tail: std::ptr::null_mut(),
+            len: 0,
+            cursor: std::ptr::null_mut(),
+            cursor_index: 0,
+        }
+    }
+
+    // メモリリークを防ぐための手動解放
+    // (ベンチマークの計測時間には含めないが、実用上必須)
+    pub fn cleanup(&mut self) {
+        unsafe {
+            let mut current = self.head;
+            while !current.is_null() {
+                let next = (*current).next;
+                // Boxに戻してDropさせる
+                let _ = Box::from_raw(current);
+                current = next;
+            }
+        }
+        // 二重解放を防ぐため、解放後は空の状態に戻す
+        self.head = std::ptr::null_mut();
+        self.tail = std::ptr::null_mut();
+        self.len = 0;
+        self.cursor = std::ptr::null_mut();
+        self.cursor_index = 0;
+    }
+
+    /// 前回の位置から再開するカーソル
+    pub fn cursor_mut(&mut self) -> UnsafeDllCursorMut<'_, T> {
+        UnsafeDllCursorMut { list: self }
+    }
+
+    /// `index` の位置（`index >= len` なら ghost）に合わせたカーソル
+    pub fn cursor_at_mut(&mut self, index: usize) -> UnsafeDllCursorMut<'_, T> {
+        let mut cursor = self.cursor_mut();
+        cursor.seek(index);
+        cursor
+    }
+
+    // index番目のノードを、head・tail・カーソルのうち一番近いところから探す
+    // (範囲外なら null)
+    fn node_at(&self, index: usize) -> *mut UnsafeNode<T> {
+        if index >= self.len {
+            return std::ptr::null_mut();
+        }
+        let mut start = (self.head, 0);
+        if self.len - 1 - index < index {
+            start = (self.tail, self.len - 1);
+        }
+        if !self.cursor.is_null() && self.cursor_index.abs_diff(index) < start.1.abs_diff(index) {
+            start = (self.cursor, self.cursor_index);
+        }
+        let (mut current, mut i) = start;
+        unsafe {
+            while i < index {
+                current = (*current).next;
+                i += 1;
+            }
+            while i > index {
+                current = (*current).prev;
+                i -= 1;
+            }
+        }
+        current
+    }
+
+    // nodeをリストから切り離して解放し、値を返す
+    // (nodeはこのリストに属する非nullのポインタであること。カーソルの調整は呼び出し側)
+    unsafe fn unlink(&mut self, node: *mut UnsafeNode<T>) -> T {
+        let prev = (*node).prev;
+        let next = (*node).next;
+        if !prev.is_null() {
+            (*prev).next = next;
+        } else {
+            self.head = next;
+        }
+        if !next.is_null() {
+            (*next).prev = prev;
+        } else {
+            self.tail = prev;
+        }
+        self.len -= 1;
+        // Boxに戻して解放
+        let boxed = Box::from_raw(node);
+        boxed.value
+    }
+
+    // prev と next の間に新しいノードを繋ぐ（どちらかが null なら端）
+    unsafe fn link_between(&mut self, prev: *mut UnsafeNode<T>, next: *mut UnsafeNode<T>, value: T) {
+        // 1. Boxで作ってヒープ確保し、即座に生ポインタにする
+        // (Rustの所有権管理から外す)
+        let new_node = Box::into_raw(Box::new(UnsafeNode { value, next, prev }));
+        if !prev.is_null() {
+            (*prev).next = new_node;
+        } else {
+            self.head = new_node;
+        }
+        if !next.is_null() {
+            (*next).prev = new_node;
+        } else {
+            self.tail = new_node;
+        }
+        self.len += 1;
+    }
+}
+
+impl<T> UnsafeDllCursorMut<'_, T> {
+    /// 指している要素の位置（ghost なら `None`）
+    pub fn index(&self) -> Option<usize> {
+        if self.list.cursor.is_null() {
+            None
+        } else {
+            Some(self.list.cursor_index)
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.list.cursor.as_mut().map(|node| &mut node.value) }
+    }
+
+    /// 次の要素へ進む（末尾の次は ghost、ghost の次は先頭）
+    pub fn move_next(&mut self) {
+        let list = &mut *self.list;
+        if list.cursor.is_null() {
+            list.cursor = list.head;
+            list.cursor_index = 0;
+        } else {
+            list.cursor = unsafe { (*list.cursor).next };
+            list.cursor_index += 1;
+        }
+    }
+
+    /// 前の要素へ戻る（先頭の前は ghost、ghost の前は末尾）
+    pub fn move_prev(&mut self) {
+        let list = &mut *self.list;
+        if list.cursor.is_null() {
+            list.cursor = list.tail;
+            list.cursor_index = list.len.saturating_sub(1);
+        } else {
+            list.cursor = unsafe { (*list.cursor).prev };
+            if list.cursor.is_null() {
+                list.cursor_index = list.len;
+            } else {
+                list.cursor_index -= 1;
+            }
+        }
+    }
+
+    /// `index` 番目へ移動する（`index >= len` なら ghost）。
+    /// head・tail・今の位置のうち一番近いところから辿る。
+    pub fn seek(&mut self, index: usize) {
+        let list = &mut *self.list;
+        list.cursor = list.node_at(index);
+        list.cursor_index = index.min(list.len);
+    }
+
+    /// 今の要素の手前に挿入する（ghost なら末尾に追加）。カーソルは今の要素のまま。
+    pub fn insert_before(&mut self, value: T) {
+        let list = &mut *self.list;
+        unsafe {
+            let next = list.cursor;
+            let prev = if next.is_null() { list.tail } else { (*next).prev };
+            list.link_between(prev, next, value);
+        }
+        // 手前に1つ増えたので、今の要素（または ghost）の位置は1つ後ろになる
+        list.cursor_index += 1;
+    }
+
+    /// 今の要素の後ろに挿入する（ghost なら先頭に追加）。カーソルは今の要素のまま。
+    pub fn insert_after(&mut self, value: T) {
+        let list = &mut *self.list;
+        unsafe {
+            let prev = list.cursor;
+            let next = if prev.is_null() { list.head } else { (*prev).next };
+            list.link_between(prev, next, value);
+        }
+        if list.cursor.is_null() {
+            list.cursor_index = list.len;
+        }
+    }
+
+    /// 今の要素を取り除いて返し、次の要素へ進む（ghost なら `None`）
+    pub fn remove_current(&mut self) -> Option<T> {
+        let list = &mut *self.list;
+        let node = list.cursor;
+        if node.is_null() {
+            return None;
+        }
+        unsafe {
+            list.cursor = (*node).next;
+            Some(list.unlink(node))
+        }
+    }
+
+    /// 今の要素より後ろを切り離して新しいリストとして返す（ghost なら全体）
+    pub fn split_after(&mut self) -> UnsafeDll<T> {
+        let list = &mut *self.list;
+        let first = if list.cursor.is_null() { list.head } else { unsafe { (*list.cursor).next } };
+        let kept = if list.cursor.is_null() { 0 } else { list.cursor_index + 1 };
+        unsafe { list.split_off_from(first, kept) }
+    }
+
+    /// 今の要素より前を切り離して新しいリストとして返す（ghost なら全体）
+    pub fn split_before(&mut self) -> UnsafeDll<T> {
+        let list = &mut *self.list;
+        if list.cursor.is_null() {
+            return std::mem::replace(list, UnsafeDll::new_empty());
+        }
+        // 今の要素から後ろを切り離して入れ替えれば、手前だけが残る
+        let cursor = list.cursor;
+        let mut rest = unsafe { list.split_off_from(cursor, list.cursor_index) };
+        rest.cursor = cursor;
+        rest.cursor_index = 0;
+        std::mem::swap(list, &mut rest);
+        rest
+    }
+}
+
+impl<T> UnsafeDll<T> {
+    // first から末尾までを新しいリストに移す（first は kept 番目のノード。null なら空）
+    // カーソルが移した側にあった場合は ghost に戻す
+    unsafe fn split_off_from(&mut self, first: *mut UnsafeNode<T>, kept: usize) -> UnsafeDll<T> {
+        let mut rest = UnsafeDll::new_empty();
+        if first.is_null() {
+            return rest;
+        }
+        let prev = (*first).prev;
+        rest.head = first;
+        rest.tail = self.tail;
+        rest.len = self.len - kept;
+        rest.cursor_index = rest.len;
+        (*first).prev = std::ptr::null_mut();
+        if prev.is_null() {
+            self.head = std::ptr::null_mut();
+        } else {
+            (*prev).next = std::ptr::null_mut();
+        }
+        self.tail = prev;
+        self.len = kept;
+        if self.cursor.is_null() || self.cursor_index >= kept {
+            self.cursor = std::ptr::null_mut();
+            self.cursor_index = kept;
+        }
+        rest
+    }
+}
+
+impl<T: Clone> SequenceBackend<T> for UnsafeDll<T> {
+    type Iter<'a> = UnsafeDllIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::new_empty()
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Self::new()
+    }
+
+    fn append(&mut self, value: T) {
+        unsafe {
+            let tail = self.tail;
+            self.link_between(tail, std::ptr::null_mut(), value);
+        }
+        // カーソルが ghost なら ghost のまま（位置は len に揃える）
+        if self.cursor.is_null() {
+            self.cursor_index = self.len;
+        }
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        // 以前は毎回headから辿っていた (O(N))。今はカーソルを一番近い端か
+        // 前回の位置から動かすので、近くへの連続した挿入はO(1)になる
+        // (末尾(範囲外含む)は ghost の手前 = append)
+        self.cursor_at_mut(index).insert_before(value);
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        self.cursor_at_mut(index).remove_current()
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        let node = self.node_at(index);
+        if node.is_null() {
+            None
+        } else {
+            unsafe { Some((*node).value.clone()) }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> UnsafeDllIter<'_, T> {
+        UnsafeDllIter {
+            current: self.head,
+            _marker: PhantomData,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cleanup();
+    }
+}
+
+impl<T> Drop for UnsafeDll<T> {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    // prev/next と tail・len、カーソルの位置が実際のノードと一致することを確かめてから、中身を比べる
+    fn check(list: &UnsafeDll, expected: &[i32]) {
+        unsafe {
+            let mut prev = std::ptr::null_mut();
+            let mut current = list.head;
+            let mut count = 0;
+            while !current.is_null() {
+                assert_eq!((*current).prev, prev);
+                if current == list.cursor {
+                    assert_eq!(list.cursor_index, count, "cursor_index does not match the cursor node");
+                }
+                prev = current;
+                current = (*current).next;
+                count += 1;
+            }
+            assert_eq!(list.tail, prev);
+            assert_eq!(count, list.len);
+        }
+        if list.cursor.is_null() {
+            assert_eq!(list.cursor_index, list.len, "ghost cursor must sit at len");
+        }
+        assert!(list.iter().eq(expected.iter().copied()));
+    }
+
+    #[test]
+    fn moves_wrap_around_through_the_ghost() {
+        let mut list: UnsafeDll = UnsafeDll::new();
+        list.extend_from_slice(&[1, 2, 3]);
+        let mut cursor = list.cursor_at_mut(0);
+        assert_eq!(cursor.index(), Some(0));
+        // 先頭の前は ghost、ghost の前は末尾
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert!(cursor.current().is_none());
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().copied(), Some(3));
+        // 末尾の次は ghost、ghost の次は先頭
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        *cursor.current().unwrap() = 10;
+        check(&list, &[10, 2, 3]);
+
+        let mut empty: UnsafeDll = UnsafeDll::new();
+        let mut cursor = empty.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        check(&empty, &[]);
+    }
+
+    #[test]
+    fn inserts_keep_the_cursor_on_its_element_or_the_ghost() {
+        let mut list: UnsafeDll = UnsafeDll::new();
+        let mut cursor = list.cursor_mut();
+        // ghost の後ろは先頭、手前は末尾
+        cursor.insert_after(2);
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+        assert_eq!(cursor.index(), None);
+        check(&list, &[1, 2, 3]);
+
+        let mut cursor = list.cursor_at_mut(1);
+        cursor.insert_before(5);
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_after(6);
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().copied(), Some(2));
+        check(&list, &[1, 5, 2, 6, 3]);
+
+        // 続けて使うと前回の位置から再開する
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.current().copied(), Some(6));
+        check(&list, &[1, 5, 2, 6, 3]);
+    }
+
+    #[test]
+    fn remove_current_moves_to_the_next_element() {
+        let mut list: UnsafeDll = UnsafeDll::new();
+        list.extend_from_slice(&[1, 2, 3]);
+        let mut cursor = list.cursor_at_mut(1);
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.index(), Some(1));
+        // 末尾を消すと ghost へ
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        check(&list, &[1]);
+
+        let mut cursor = list.cursor_at_mut(0);
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.index(), None);
+        check(&list, &[]);
+    }
+
+    #[test]
+    fn split_after_keeps_the_cursor_in_the_front_half() {
+        let mut list: UnsafeDll = UnsafeDll::new();
+        list.extend_from_slice(&[0, 1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_at_mut(2);
+        let mut back = cursor.split_after();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().copied(), Some(2));
+        check(&list, &[0, 1, 2]);
+        // 切り離した側のカーソルは ghost
+        assert_eq!(back.cursor_mut().index(), None);
+        check(&back, &[3, 4, 5]);
+
+        // どちらもそのまま使える
+        list.append(9);
+        back.insert(0, 8);
+        check(&list, &[0, 1, 2, 9]);
+        check(&back, &[8, 3, 4, 5]);
+
+        // 末尾で切ると空、ghost で切ると全体
+        let mut cursor = list.cursor_at_mut(3);
+        assert_eq!(cursor.split_after().len(), 0);
+        let mut cursor = list.cursor_at_mut(usize::MAX);
+        let all = cursor.split_after();
+        check(&list, &[]);
+        check(&all, &[0, 1, 2, 9]);
+    }
+
+    #[test]
+    fn split_before_leaves_the_cursor_at_the_new_head() {
+        let mut list: UnsafeDll = UnsafeDll::new();
+        list.extend_from_slice(&[0, 1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_at_mut(3);
+        let mut front = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current().copied(), Some(3));
+        check(&list, &[3, 4, 5]);
+        // カーソルは切り離した側にいたので、前半は ghost に戻る
+        assert_eq!(front.cursor_mut().index(), None);
+        check(&front, &[0, 1, 2]);
+
+        front.append(7);
+        list.insert(1, 6);
+        check(&front, &[0, 1, 2, 7]);
+        check(&list, &[3, 6, 4, 5]);
+
+        // 先頭で切ると空、ghost で切ると全体
+        let mut cursor = list.cursor_at_mut(0);
+        assert_eq!(cursor.split_before().len(), 0);
+        check(&list, &[3, 6, 4, 5]);
+        let mut cursor = list.cursor_at_mut(4);
+        let all = cursor.split_before();
+        check(&list, &[]);
+        check(&all, &[3, 6, 4, 5]);
+    }
+
+    #[test]
+    fn both_halves_drop_their_elements() {
+        let token = Rc::new(());
+        let mut list: UnsafeDll<Rc<()>> = UnsafeDll::new();
+        for _ in 0..6 {
+            list.append(token.clone());
+        }
+        let back = list.cursor_at_mut(3).split_after();
+        let front = list.cursor_at_mut(1).split_before();
+        assert_eq!((front.len(), list.len(), back.len()), (1, 3, 2));
+        assert_eq!(Rc::strong_count(&token), 7);
+        drop(front);
+        drop(back);
+        assert_eq!(Rc::strong_count(&token), 4);
+        drop(list);
+        assert_eq!(Rc::strong_count(&token), 1);
+    }
+}
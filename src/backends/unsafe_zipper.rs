@@ -0,0 +1,212 @@
+use std::iter::Sum;
+use std::ptr;
+
+use rayon::prelude::*;
+
+use crate::{AllocError, SequenceBackend};
+
+use super::zipper::ZipperIter;
+
+// ========================================================
+// Rust (Unsafe Zipper) Implementation
+// Impl: Unsafe Vec ops mimicking Zig's manual memory move
+// ========================================================
+
+pub struct UnsafeZipperList<T = i32> {
+    left: Vec<T>,
+    right: Vec<T>,
+}
+
+impl<T> UnsafeZipperList<T> {
+    // カーソルを index の位置へまとめて移動する
+    #[inline(always)]
+    fn seek(&mut self, index: usize) {
+        unsafe {
+            let left_len = self.left.len();
+            if index < left_len {
+                // --- Move from Left to Right ---
+                let count = left_len - index;
+                // with_capacity の見積もりを超えた場合に備えて確保しておく
+                self.right.reserve(count);
+                
+                // ポインタ取得
+                let left_ptr = self.left.as_ptr().add(index);
+                let right_ptr = self.right.as_mut_ptr().add(self.right.len());
+                
+                // 【修正】Write Forward (順方向書き込み) に変更
+                // 左スタックの末尾(top)から順に、右スタックの末尾(new top)へ書き込む
+                for i in 0..count {
+                    // Right(Dest): 前から順に書き込む (Cache Friendly!)
+                    // Left(Src)  : 後ろから順に読む
+                    // (要素は移動なので、書き込み先の未初期化領域をDropしない ptr::write を使う)
+                    ptr::write(right_ptr.add(i), ptr::read(left_ptr.add(count - 1 - i)));
+                }
+                
+                self.right.set_len(self.right.len() + count);
+                self.left.set_len(index);
+
+            } else if index > left_len {
+                // --- Move from Right to Left ---
+                let count = index - left_len;
+                let right_len = self.right.len();
+                let move_count = if count > right_len { right_len } else { count };
+                self.left.reserve(move_count);
+                
+                let start_index = right_len - move_count;
+                let right_ptr = self.right.as_ptr().add(start_index);
+                let left_ptr = self.left.as_mut_ptr().add(self.left.len());
+                
+                // 【修正】Write Forward (順方向書き込み) に変更
+                for i in 0..move_count {
+                     // Left(Dest): 前から順に書き込む
+                     // Right(Src): 後ろから順に読む
+                     ptr::write(left_ptr.add(i), ptr::read(right_ptr.add(move_count - 1 - i)));
+                }
+                
+                self.left.set_len(self.left.len() + move_count);
+                self.right.set_len(start_index);
+            }
+        }
+    }
+}
+
+impl<T: Clone> SequenceBackend<T> for UnsafeZipperList<T> {
+    type Iter<'a> = ZipperIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            left: Vec::with_capacity(capacity),
+            right: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn append(&mut self, value: T) {
+        if !self.right.is_empty() {
+            self.seek(self.len());
+        }
+        self.left.push(value);
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        if !self.right.is_empty() {
+            self.seek(self.len());
+        }
+        // 1回の reserve と、Copy な要素なら1回の memcpy
+        self.left.extend_from_slice(values);
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn insert(&mut self, index: usize, value: T) {
+        self.seek(index);
+        // Push
+        // Pre-allocation済みなのでreserveチェックなしで書くことも可能だが
+        // pushの実装は十分に速いのでこのまま
+        self.left.push(value);
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.seek(index);
+        self.right.pop()
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        // カーソルを先頭に寄せれば、先頭要素は右スタックのtop
+        self.seek(0);
+        self.right.pop()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        // カーソルを末尾に寄せれば、末尾要素は左スタックのtop
+        self.seek(self.len());
+        self.left.pop()
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        let left_len = self.left.len();
+        if index < left_len {
+            Some(self.left[index].clone())
+        } else {
+            let offset = index - left_len;
+            let right_len = self.right.len();
+            if offset < right_len {
+                Some(self.right[right_len - 1 - offset].clone())
+            } else {
+                None
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.left.len() + self.right.len()
+    }
+
+    fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        let left_sum: T = self.left.iter().cloned().sum();
+        let right_sum: T = self.right.iter().cloned().sum();
+        [left_sum, right_sum].into_iter().sum()
+    }
+
+    fn iter(&self) -> ZipperIter<'_, T> {
+        self.left.iter().chain(self.right.iter().rev()).cloned()
+    }
+
+    fn clear(&mut self) {
+        self.left.clear();
+        self.right.clear();
+    }
+}
+
+// --------------------------------------------------------
+// Parallel (rayon)
+// --------------------------------------------------------
+
+/// 1タスクでコピーする要素数
+const PAR_CHUNK: usize = 64 * 1024;
+
+impl<T: Copy + Send + Sync> UnsafeZipperList<T> {
+    /// `values` をチャンクごとに並列に memcpy して作る（カーソルは末尾）
+    pub fn par_from_slice(values: &[T]) -> Self {
+        let mut left: Vec<T> = Vec::with_capacity(values.len());
+        left.spare_capacity_mut()[..values.len()]
+            .par_chunks_mut(PAR_CHUNK)
+            .zip(values.par_chunks(PAR_CHUNK))
+            .for_each(|(dst, src)| unsafe {
+                // dst と src は同じ長さで重ならない
+                ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr() as *mut T, src.len());
+            });
+        // 0..len はすべてコピー済み
+        unsafe { left.set_len(values.len()) };
+        Self { left, right: Vec::new() }
+    }
+
+    /// `sum()` の並列版
+    pub fn par_sum(&self) -> T
+    where
+        T: Sum,
+    {
+        self.left.par_iter().chain(self.right.par_iter()).copied().sum()
+    }
+
+    /// `sum_i64()` の並列版
+    pub fn par_sum_i64(&self) -> i64
+    where
+        T: Into<i64>,
+    {
+        self.left.par_iter().chain(self.right.par_iter()).map(|&value| value.into()).sum()
+    }
+}
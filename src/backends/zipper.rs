@@ -0,0 +1,319 @@
+use std::iter::Sum;
+
+use rayon::prelude::*;
+
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Rust (Zipper) Implementation
+// Impl: Two Stacks (Vec) based Cursor
+// ========================================================
+
+pub struct ZipperList<T = i32> {
+    left: Vec<T>,  // カーソルより左にある要素（スタック）
+    right: Vec<T>, // カーソルより右にある要素（スタック）
+}
+
+/// 左スタックを前から、右スタックを後ろから読む
+pub type ZipperIter<'a, T = i32> = std::iter::Cloned<
+    std::iter::Chain<std::slice::Iter<'a, T>, std::iter::Rev<std::slice::Iter<'a, T>>>,
+>;
+
+// --------------------------------------------------------
+// Cursor API
+// カーソルは要素と要素の間（0..=len）にあり、position() は左スタックの長さ。
+// エディタのキャレットと同じで、before は左隣・after は右隣の要素を指す。
+// --------------------------------------------------------
+
+impl<T> ZipperList<T> {
+    /// カーソルの位置（左にある要素数）
+    pub fn position(&self) -> usize {
+        self.left.len()
+    }
+
+    /// カーソルを1つ左へ移動する（先頭なら false）
+    pub fn move_left(&mut self) -> bool {
+        match self.left.pop() {
+            Some(val) => {
+                self.right.push(val);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// カーソルを1つ右へ移動する（末尾なら false）
+    pub fn move_right(&mut self) -> bool {
+        match self.right.pop() {
+            Some(val) => {
+                self.left.push(val);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// カーソルを index の位置へまとめて移動する（`index > len` は末尾）
+    pub fn seek(&mut self, index: usize) {
+        let current_pos = self.left.len();
+
+        if index < current_pos {
+            // 左にある -> まとめて右へ移動
+            // 1つずつpop/pushせず、drainで一気に移動させる
+            // (右スタックは逆順に並ぶので rev() して積む)
+            self.right.extend(self.left.drain(index..).rev());
+        } else if index > current_pos {
+            // 右にある -> まとめて左へ移動
+            // 右スタックの末尾(=カーソル直近)から取り出して左へ
+            let start = self.right.len().saturating_sub(index - current_pos);
+            self.left.extend(self.right.drain(start..).rev());
+        }
+    }
+
+    /// カーソルの左隣の要素
+    pub fn peek_before(&self) -> Option<&T> {
+        self.left.last()
+    }
+
+    /// カーソルの右隣の要素
+    pub fn peek_after(&self) -> Option<&T> {
+        self.right.last()
+    }
+
+    /// 左隣の要素を置き換えて、元の値を返す（先頭なら何もしない）
+    pub fn replace_before(&mut self, value: T) -> Option<T> {
+        self.left.last_mut().map(|slot| std::mem::replace(slot, value))
+    }
+
+    /// 右隣の要素を置き換えて、元の値を返す（末尾なら何もしない）
+    pub fn replace_after(&mut self, value: T) -> Option<T> {
+        self.right.last_mut().map(|slot| std::mem::replace(slot, value))
+    }
+
+    /// 左隣に挿入する（タイプ入力と同じで、カーソルは挿入した要素の右に来る）
+    pub fn insert_before(&mut self, value: T) {
+        self.left.push(value);
+    }
+
+    /// 右隣に挿入する（カーソルは動かない）
+    pub fn insert_after(&mut self, value: T) {
+        self.right.push(value);
+    }
+
+    /// 左隣の要素を削除する（Backspace）
+    pub fn delete_before(&mut self) -> Option<T> {
+        self.left.pop()
+    }
+
+    /// 右隣の要素を削除する（Delete）
+    pub fn delete_after(&mut self) -> Option<T> {
+        self.right.pop()
+    }
+}
+
+impl<T: Clone> SequenceBackend<T> for ZipperList<T> {
+    type Iter<'a> = ZipperIter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            left: Vec::with_capacity(capacity),
+            right: Vec::with_capacity(capacity),
+        }
+    }
+
+    // 末尾への追加 = カーソルが末尾にある状態での左スタックへのPush
+    fn append(&mut self, value: T) {
+        if !self.right.is_empty() {
+            // カーソルが途中にある場合は末尾まで移動してから
+            self.seek(self.len());
+        }
+        self.left.push(value);
+    }
+
+    fn extend_from_slice(&mut self, values: &[T]) {
+        if !self.right.is_empty() {
+            self.seek(self.len());
+        }
+        // left の末尾へまとめてコピー（Copy な要素なら1回の memcpy）
+        self.left.extend_from_slice(values);
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError> {
+        self.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        // 2つのベクタの合計を足すだけ
+        // メモリ上で連続しているため、CPUキャッシュが効きまくる
+        let left_sum: T = self.left.iter().cloned().sum();
+        let right_sum: T = self.right.iter().cloned().sum();
+        [left_sum, right_sum].into_iter().sum()
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        self.seek(index);
+        self.insert_before(value);
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        // カーソルを index に合わせると、対象は右スタックのtop
+        self.seek(index);
+        self.delete_after()
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        // カーソルを先頭に寄せれば、先頭要素は右スタックのtop
+        self.seek(0);
+        self.right.pop()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        // カーソルを末尾に寄せれば、末尾要素は左スタックのtop
+        self.seek(self.len());
+        self.left.pop()
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        let left_len = self.left.len();
+        if index < left_len {
+            Some(self.left[index].clone())
+        } else {
+            // 右スタックは逆順に並んでいる
+            let offset = index - left_len;
+            let right_len = self.right.len();
+            if offset < right_len {
+                Some(self.right[right_len - 1 - offset].clone())
+            } else {
+                None
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.left.len() + self.right.len()
+    }
+
+    fn iter(&self) -> ZipperIter<'_, T> {
+        self.left.iter().chain(self.right.iter().rev()).cloned()
+    }
+
+    fn clear(&mut self) {
+        self.left.clear();
+        self.right.clear();
+    }
+}
+
+// --------------------------------------------------------
+// Parallel (rayon)
+// 呼び出したスレッドプール（`parallel::with_threads`）のスレッド数で動く
+// --------------------------------------------------------
+
+impl<T: Clone + Send + Sync> ZipperList<T> {
+    /// `values` を並列に複製して作る（カーソルは末尾）
+    pub fn par_from_slice(values: &[T]) -> Self {
+        Self {
+            left: values.par_iter().cloned().collect(),
+            right: Vec::new(),
+        }
+    }
+
+    /// `sum()` の並列版（左右のスタックを分割して各スレッドで足す）
+    pub fn par_sum(&self) -> T
+    where
+        T: Sum,
+    {
+        self.left.par_iter().chain(self.right.par_iter()).cloned().sum()
+    }
+
+    /// `sum_i64()` の並列版
+    pub fn par_sum_i64(&self) -> i64
+    where
+        T: Into<i64>,
+    {
+        self.left.par_iter().chain(self.right.par_iter()).map(|value| value.clone().into()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[i32]) -> ZipperList {
+        ZipperList::from_slice(values)
+    }
+
+    #[test]
+    fn seek_moves_in_both_directions_and_clamps_to_the_end() {
+        let mut zipper = list(&[0, 1, 2, 3, 4, 5]);
+        assert_eq!(zipper.position(), 6);
+        zipper.seek(2);
+        assert_eq!((zipper.position(), zipper.peek_before(), zipper.peek_after()), (2, Some(&1), Some(&2)));
+        zipper.seek(5);
+        assert_eq!((zipper.position(), zipper.peek_before(), zipper.peek_after()), (5, Some(&4), Some(&5)));
+        zipper.seek(0);
+        assert_eq!((zipper.position(), zipper.peek_before(), zipper.peek_after()), (0, None, Some(&0)));
+        zipper.seek(3);
+        zipper.seek(3);
+        assert_eq!(zipper.position(), 3);
+        // 右スタックより遠くを指定しても末尾で止まる（差が右の長さを超える場合）
+        zipper.seek(100);
+        assert_eq!((zipper.position(), zipper.peek_after()), (6, None));
+        zipper.seek(1);
+        zipper.seek(usize::MAX);
+        assert_eq!(zipper.position(), 6);
+        assert!(zipper.iter().eq(0..6));
+    }
+
+    #[test]
+    fn moves_stop_at_both_ends() {
+        let mut zipper = list(&[1, 2]);
+        assert!(!zipper.move_right());
+        assert!(zipper.move_left());
+        assert!(zipper.move_left());
+        assert!(!zipper.move_left());
+        assert_eq!(zipper.position(), 0);
+        assert!(zipper.move_right());
+        assert_eq!(zipper.peek_before(), Some(&1));
+        assert!(zipper.iter().eq([1, 2]));
+    }
+
+    #[test]
+    fn replace_and_delete_act_on_the_neighbours() {
+        let mut zipper = list(&[1, 2, 3]);
+        zipper.seek(1);
+        assert_eq!(zipper.replace_before(10), Some(1));
+        assert_eq!(zipper.replace_after(20), Some(2));
+        assert_eq!(zipper.position(), 1);
+        assert!(zipper.iter().eq([10, 20, 3]));
+
+        assert_eq!(zipper.delete_after(), Some(20));
+        assert_eq!(zipper.delete_before(), Some(10));
+        assert_eq!(zipper.position(), 0);
+        assert!(zipper.iter().eq([3]));
+
+        // 端では何もしない
+        assert_eq!(zipper.replace_before(7), None);
+        assert_eq!(zipper.delete_before(), None);
+        zipper.seek(1);
+        assert_eq!(zipper.replace_after(7), None);
+        assert_eq!(zipper.delete_after(), None);
+        assert!(zipper.iter().eq([3]));
+
+        // 挿入後のカーソルはタイプ入力と同じ
+        zipper.insert_before(4);
+        zipper.insert_after(5);
+        assert_eq!(zipper.position(), 2);
+        assert!(zipper.iter().eq([3, 4, 5]));
+    }
+}
@@ -0,0 +1,356 @@
+use std::hint::black_box;
+
+use serde::{Deserialize, Serialize};
+
+use crate::workload::{run_ops, try_run_ops, Workload};
+use crate::{with_backend, AllocError, BackendKind, Checksum, SequenceBackend};
+
+// ========================================================
+// Benchmark Runner
+// ウォームアップ → 適応的なサンプル数 → 中央値/p95/ブートストラップ信頼区間
+// 構築(+操作)とDrop(cleanup)は別々に計測する
+// ========================================================
+
+// --------------------------------------------------------
+// Clock
+// --------------------------------------------------------
+
+// ブラウザ/Node では std::time::Instant が使えないので performance.now() を使う
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod clock {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        fn performance_now() -> f64;
+    }
+
+    pub fn now_ms() -> f64 {
+        performance_now()
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+mod clock {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+
+    pub fn now_ms() -> f64 {
+        START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+pub use clock::now_ms;
+
+// --------------------------------------------------------
+// Config
+// --------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// 計測前に捨てる実行回数（キャッシュ・分岐予測・JITを温める）
+    pub warmup: usize,
+    pub min_samples: usize,
+    pub max_samples: usize,
+    /// 平均の標準誤差 / 平均 がこの値を下回ったらサンプリングを打ち切る
+    pub target_rel_error: f64,
+    /// ウォームアップ後の計測にかける時間の上限（ミリ秒）
+    pub max_time_ms: f64,
+    /// 信頼区間のブートストラップ再標本化回数
+    pub bootstrap_resamples: usize,
+    /// 信頼水準 (0.95 なら 95% CI)
+    pub confidence: f64,
+    /// ブートストラップ用の乱数シード（結果を再現可能にする）
+    pub seed: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            min_samples: 10,
+            max_samples: 200,
+            target_rel_error: 0.01,
+            max_time_ms: 10_000.0,
+            bootstrap_resamples: 2_000,
+            confidence: 0.95,
+            seed: 0x5eed,
+        }
+    }
+}
+
+// --------------------------------------------------------
+// Statistics
+// --------------------------------------------------------
+
+/// 1つのフェーズの計測結果（単位はミリ秒）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    /// Tukeyの柵 (Q3 + 1.5 IQR / Q1 - 1.5 IQR) の外にあったサンプル数
+    pub outliers: usize,
+    pub min: f64,
+    pub max: f64,
+    /// 外れ値を除いた平均
+    pub mean: f64,
+    pub stddev: f64,
+    pub median: f64,
+    pub p95: f64,
+    /// 中央値のブートストラップ信頼区間
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[f64], config: &BenchConfig) -> Stats {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let (lo, hi) = (q1 - fence, q3 + fence);
+        let kept: Vec<f64> = sorted.iter().copied().filter(|x| *x >= lo && *x <= hi).collect();
+
+        let (mean, stddev) = mean_stddev(&kept);
+        let (ci_low, ci_high) = bootstrap_median_ci(&sorted, config);
+
+        Stats {
+            samples: sorted.len(),
+            outliers: sorted.len() - kept.len(),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean,
+            stddev,
+            median: percentile(&sorted, 0.5),
+            p95: percentile(&sorted, 0.95),
+            ci_low,
+            ci_high,
+        }
+    }
+}
+
+// 線形補間のパーセンタイル（sorted は昇順）
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+    let pos = q * (sorted.len() - 1) as f64;
+    let i = pos.floor() as usize;
+    let frac = pos - i as f64;
+    if i + 1 < sorted.len() {
+        sorted[i] + (sorted[i + 1] - sorted[i]) * frac
+    } else {
+        sorted[i]
+    }
+}
+
+fn mean_stddev(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let var = values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+    (mean, var.sqrt())
+}
+
+// xorshift64* (ブートストラップの再標本化用。暗号強度は不要)
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+}
+
+fn bootstrap_median_ci(sorted: &[f64], config: &BenchConfig) -> (f64, f64) {
+    let n = sorted.len();
+    if n < 2 || config.bootstrap_resamples == 0 {
+        let m = percentile(sorted, 0.5);
+        return (m, m);
+    }
+    let mut rng = XorShift(config.seed | 1);
+    let mut medians = Vec::with_capacity(config.bootstrap_resamples);
+    let mut resample = vec![0.0; n];
+    for _ in 0..config.bootstrap_resamples {
+        for slot in resample.iter_mut() {
+            *slot = sorted[(rng.next() % n as u64) as usize];
+        }
+        resample.sort_by(|a, b| a.total_cmp(b));
+        medians.push(percentile(&resample, 0.5));
+    }
+    medians.sort_by(|a, b| a.total_cmp(b));
+    let alpha = (1.0 - config.confidence) / 2.0;
+    (percentile(&medians, alpha), percentile(&medians, 1.0 - alpha))
+}
+
+// --------------------------------------------------------
+// Runner
+// --------------------------------------------------------
+
+/// 構築と解放を分けて計測した結果
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    /// 構築＋操作（Dropを含まない）
+    pub build: Stats,
+    /// Drop (UnsafeDll なら cleanup)
+    pub teardown: Stats,
+    /// build + teardown
+    pub total: Stats,
+}
+
+/// `run` を繰り返し計測する。`run` が返した値のDropは teardown として別に計る。
+pub fn measure<T, F: FnMut() -> T>(config: &BenchConfig, mut run: F) -> Measurement {
+    for _ in 0..config.warmup {
+        drop(black_box(run()));
+    }
+
+    let mut build = Vec::new();
+    let mut teardown = Vec::new();
+    let mut total = Vec::new();
+    let started = now_ms();
+    loop {
+        let t0 = now_ms();
+        let value = black_box(run());
+        let t1 = now_ms();
+        drop(value);
+        let t2 = now_ms();
+
+        build.push(t1 - t0);
+        teardown.push(t2 - t1);
+        total.push(t2 - t0);
+
+        let n = total.len();
+        if n >= config.max_samples || (n >= config.min_samples && now_ms() - started >= config.max_time_ms) {
+            break;
+        }
+        if n >= config.min_samples {
+            // 平均の相対標準誤差が十分小さくなったら打ち切る
+            let (mean, stddev) = mean_stddev(&total);
+            if mean > 0.0 && stddev / (n as f64).sqrt() / mean <= config.target_rel_error {
+                break;
+            }
+        }
+    }
+
+    Measurement {
+        build: Stats::from_samples(&build, config),
+        teardown: Stats::from_samples(&teardown, config),
+        total: Stats::from_samples(&total, config),
+    }
+}
+
+/// バックエンド1つ分のベンチマーク結果
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchResult {
+    pub backend: BackendKind,
+    pub measurement: Measurement,
+    /// 操作を流し終えたリストのチェックサム（実装間・ランタイム間で一致するはず）
+    pub checksum: Checksum,
+}
+
+/// 計測前に1回 `try_run_ops` で流し、容量不足ならその時点でエラーにする
+/// （同じ操作列なので、1回通れば計測中の実行も通る）。チェックサムはこの1回のリストから取る
+pub fn bench_backend<B: SequenceBackend>(
+    workload: &Workload,
+    config: &BenchConfig,
+) -> Result<(Measurement, Checksum), AllocError> {
+    let ops = workload.generate();
+    let capacity = workload.prefill + workload.ops;
+    let mut list = B::with_capacity(capacity);
+    try_run_ops(&mut list, ops.iter().copied())?;
+    let checksum = list.checksum();
+    drop(list);
+
+    let measurement = measure(config, || {
+        let mut list = B::with_capacity(capacity);
+        black_box(run_ops(&mut list, ops.iter().copied()));
+        list
+    });
+    Ok((measurement, checksum))
+}
+
+pub fn bench(kind: BackendKind, workload: &Workload, config: &BenchConfig) -> Result<BenchResult, AllocError> {
+    let (measurement, checksum) = with_backend!(kind, B => bench_backend::<B>(workload, config))?;
+    Ok(BenchResult { backend: kind, measurement, checksum })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1..=10 と、Tukeyの柵 (Q3 + 1.5 IQR = 16) を大きく超える 100
+    const SAMPLES: [f64; 11] = [7.0, 3.0, 100.0, 1.0, 9.0, 2.0, 10.0, 5.0, 4.0, 8.0, 6.0];
+
+    #[test]
+    fn counts_tukey_outliers_on_both_sides() {
+        let config = BenchConfig::default();
+        let stats = Stats::from_samples(&SAMPLES, &config);
+        assert_eq!(stats.samples, 11);
+        assert_eq!(stats.outliers, 1);
+        // 外れ値を除いた 1..=10 の平均と標本標準偏差
+        assert!((stats.mean - 5.5).abs() < 1e-12);
+        assert!((stats.stddev - (55.0f64 / 6.0).sqrt()).abs() < 1e-12);
+
+        let mut both = SAMPLES.to_vec();
+        both.push(-50.0);
+        assert_eq!(Stats::from_samples(&both, &config).outliers, 2);
+
+        // 柵のちょうど上は外れ値ではない（Q1 = 3.5, Q3 = 8.5 → 柵は -4 と 16）
+        let mut edge = SAMPLES.to_vec();
+        edge[2] = 16.0;
+        assert_eq!(Stats::from_samples(&edge, &config).outliers, 0);
+    }
+
+    #[test]
+    fn median_and_p95_interpolate_linearly() {
+        let stats = Stats::from_samples(&SAMPLES, &BenchConfig::default());
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 100.0);
+        // 11個の昇順の 5番目と、9.5番目（10 と 100 の中間）
+        assert_eq!(stats.median, 6.0);
+        assert_eq!(stats.p95, 55.0);
+
+        let even = Stats::from_samples(&[4.0, 1.0, 3.0, 2.0], &BenchConfig::default());
+        assert_eq!(even.median, 2.5);
+        assert!((even.p95 - 3.85).abs() < 1e-12);
+    }
+
+    #[test]
+    fn median_lies_inside_its_bootstrap_ci() {
+        let config = BenchConfig::default();
+        let stats = Stats::from_samples(&SAMPLES, &config);
+        assert!(stats.ci_low <= stats.median && stats.median <= stats.ci_high, "{:?}", stats);
+        assert!(stats.ci_low >= stats.min && stats.ci_high <= stats.max);
+        // 同じシードなら同じ区間
+        assert_eq!(Stats::from_samples(&SAMPLES, &config), stats);
+
+        // 全部同じ値なら区間は1点
+        let flat = Stats::from_samples(&[2.0; 20], &config);
+        assert_eq!((flat.ci_low, flat.median, flat.ci_high), (2.0, 2.0, 2.0));
+        // 1サンプルや再標本化0回は中央値そのもの
+        let one = Stats::from_samples(&[3.0], &config);
+        assert_eq!((one.ci_low, one.ci_high), (3.0, 3.0));
+        let no_bootstrap = BenchConfig { bootstrap_resamples: 0, ..config };
+        let stats = Stats::from_samples(&SAMPLES, &no_bootstrap);
+        assert_eq!((stats.ci_low, stats.ci_high), (6.0, 6.0));
+    }
+
+    #[test]
+    fn empty_input_gives_default_stats() {
+        assert_eq!(Stats::from_samples(&[], &BenchConfig::default()), Stats::default());
+    }
+}
@@ -0,0 +1,426 @@
+// ========================================================
+// pcl: Python も ブラウザ も介さずにベンチマークを回すCLI
+// ========================================================
+//
+//   pcl bench --impl zipper,bump --workload random-insert --n 30000 --repeat 20 --format json
+//   pcl bench --wasm www/zig_zipper.wasm:run_zig_zipper --workload random-insert   (要 --features wasm-runner)
+//   pcl compare baseline.json candidate.json --threshold 5
+//   pcl checksum --workload append --n 100000 --wasm www/zig_dll.wasm:run_zig_dll   (要 --features wasm-runner)
+//
+// pyo3 の呼び出しオーバーヘッドが乗らない「素の」数字を取るためのもの。
+
+use std::collections::HashMap;
+use std::fs;
+use std::process::ExitCode;
+
+use polyglot_compute_lab::bench::{bench, BenchConfig};
+use polyglot_compute_lab::compare::compare;
+use polyglot_compute_lab::harness::try_workload_checksum;
+use polyglot_compute_lab::report::{self, BenchmarkReport, Runtime};
+use polyglot_compute_lab::{with_backend, BackendKind, Checksum, Workload};
+
+const USAGE: &str = "\
+usage:
+  pcl bench [options]
+      --impl LIST        comma separated backends or 'all' (safe,unsafe,bump,bump-growable,zipper,unsafe-zipper,gap-buffer,unrolled,treap,skiplist,arena-dll,soa-bump) [all]
+      --workload NAME    random-insert | append | editor | queue | append=1,insert=3,... [random-insert]
+      --dist DIST        uniform | front | back | zipf[:s] | sequential[:step] | head | tail [uniform]
+      --n N              number of operations [30000]
+      --seed SEED        workload seed [123456789]
+      --prefill N        elements appended before the measured ops [0]
+      --repeat N         fixed number of samples (default: adaptive 10..200)
+      --warmup N         discarded warmup runs [3]
+      --max-seconds S    time budget per implementation [10]
+      --wasm FILE:FUNC   also time an exported `fn(i32) -> i32` through wasmtime (repeatable)
+      --format FMT       table | json | csv [table]
+      --out FILE         write to FILE instead of stdout
+  pcl compare BASELINE.json CANDIDATE.json [--threshold PCT] [--format table|json]
+  pcl checksum [--impl LIST] [--workload NAME] [--dist DIST] [--n N] [--seed SEED] [--prefill N] [--wasm FILE:FUNC]...
+      runs every implementation once and compares length, i64 sum and rolling hash of the final list
+      (wasm modules must export checksum_len / checksum_sum / checksum_hash) [--workload append --n 100000]
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("bench") => cmd_bench(&args[1..]),
+        Some("compare") => cmd_compare(&args[1..]),
+        Some("checksum") => cmd_checksum(&args[1..]),
+        Some("-h") | Some("--help") | Some("help") => {
+            print!("{}", USAGE);
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Err(format!("expected a subcommand\n\n{}", USAGE)),
+    };
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("pcl: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+// --------------------------------------------------------
+// Argument Parsing
+// --------------------------------------------------------
+
+// `--key value` / `--key=value` を集める（--wasm のように複数回指定できるものは全て残す）
+struct Args {
+    options: HashMap<String, Vec<String>>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: &[String], known: &[&str]) -> Result<Self, String> {
+        let mut options: HashMap<String, Vec<String>> = HashMap::new();
+        let mut positional = Vec::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                positional.push(arg.clone());
+                continue;
+            };
+            let (key, value) = match flag.split_once('=') {
+                Some((k, v)) => (k.to_string(), v.to_string()),
+                None => {
+                    let v = iter.next().ok_or_else(|| format!("--{} needs a value", flag))?;
+                    (flag.to_string(), v.clone())
+                }
+            };
+            if !known.contains(&key.as_str()) {
+                return Err(format!("unknown option --{}\n\n{}", key, USAGE));
+            }
+            options.entry(key).or_default().push(value);
+        }
+        Ok(Self { options, positional })
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.options.get(key).and_then(|v| v.last()).map(String::as_str)
+    }
+
+    fn all(&self, key: &str) -> &[String] {
+        self.options.get(key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    fn number<T: std::str::FromStr>(&self, key: &str, default: T) -> Result<T, String> {
+        match self.get(key) {
+            Some(v) => v.parse().map_err(|_| format!("invalid value '{}' for --{}", v, key)),
+            None => Ok(default),
+        }
+    }
+}
+
+fn parse_kinds(args: &Args) -> Result<Vec<BackendKind>, String> {
+    match args.get("impl").unwrap_or("all") {
+        "all" => Ok(BackendKind::ALL.to_vec()),
+        list => list
+            .split(',')
+            .map(|name| BackendKind::from_name(name.trim()).ok_or_else(|| format!("unknown backend '{}'", name)))
+            .collect(),
+    }
+}
+
+fn parse_workload(args: &Args, default_workload: &str, default_n: usize) -> Result<Workload, String> {
+    // 旧 random insert ベンチマークの名前も受け付ける
+    let mix = match args.get("workload").unwrap_or(default_workload) {
+        "random-insert" => "insert",
+        other => other,
+    };
+    Ok(Workload::from_spec(
+        args.number("n", default_n)?,
+        args.number("seed", 123_456_789)?,
+        mix,
+        args.get("dist").unwrap_or("uniform"),
+    )?
+    .with_prefill(args.number("prefill", 0)?))
+}
+
+fn write_output(args: &Args, text: &str) -> Result<(), String> {
+    match args.get("out") {
+        Some(path) => fs::write(path, text).map_err(|e| format!("{}: {}", path, e)),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+// --------------------------------------------------------
+// pcl bench
+// --------------------------------------------------------
+
+fn cmd_bench(raw: &[String]) -> Result<ExitCode, String> {
+    let args = Args::parse(
+        raw,
+        &[
+            "impl", "workload", "dist", "n", "seed", "prefill", "repeat", "warmup", "max-seconds", "wasm", "format", "out",
+        ],
+    )?;
+
+    let kinds = parse_kinds(&args)?;
+    let workload = parse_workload(&args, "random-insert", 30_000)?;
+
+    let mut config = BenchConfig {
+        warmup: args.number("warmup", 3)?,
+        max_time_ms: args.number("max-seconds", 10.0)? * 1000.0,
+        ..BenchConfig::default()
+    };
+    if let Some(repeat) = args.get("repeat") {
+        let repeat: usize = repeat.parse().map_err(|_| format!("invalid value '{}' for --repeat", repeat))?;
+        config.min_samples = repeat.max(1);
+        config.max_samples = repeat.max(1);
+        config.max_time_ms = f64::INFINITY;
+    }
+
+    let format = args.get("format").unwrap_or("table");
+    if !matches!(format, "table" | "json" | "csv") {
+        return Err(format!("unknown format '{}' (expected table, json or csv)", format));
+    }
+
+    let mut wasm_benches = args
+        .all("wasm")
+        .iter()
+        .map(|spec| wasm::load(spec))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut reports = Vec::new();
+    for kind in kinds {
+        if format == "table" {
+            eprintln!("running {} ...", kind.name());
+        }
+        let result = bench(kind, &workload, &config).map_err(|e| format!("{}: {}", kind.name(), e))?;
+        reports.push(BenchmarkReport::from_result(&result, &workload, Runtime::Native));
+    }
+    for wasm in &mut wasm_benches {
+        if format == "table" {
+            eprintln!("running {} (wasmtime) ...", wasm.name());
+        }
+        reports.push(wasm.bench(&workload, &config)?);
+    }
+
+    let text = match format {
+        "json" => report::to_json(&reports) + "\n",
+        "csv" => report::to_csv(&reports),
+        _ => table(&reports),
+    };
+    write_output(&args, &text)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn table(reports: &[BenchmarkReport]) -> String {
+    let mut out = format!(
+        "{:<20} {:<9} {:>11} {:>11} {:>23} {:>12} {:>8}\n",
+        "implementation", "runtime", "median(ms)", "p95(ms)", "95% CI", "drop(ms)", "samples"
+    );
+    out.push_str(&"-".repeat(99));
+    out.push('\n');
+    for r in reports {
+        let drop = r.teardown.map(|t| format!("{:.3}", t.median)).unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "{:<20} {:<9} {:>11.3} {:>11.3} {:>23} {:>12} {:>8}\n",
+            r.implementation,
+            r.runtime.name(),
+            r.total.median,
+            r.total.p95,
+            format!("[{:.3}, {:.3}]", r.total.ci_low, r.total.ci_high),
+            drop,
+            r.total.samples
+        ));
+    }
+    out
+}
+
+// --------------------------------------------------------
+// pcl checksum
+// --------------------------------------------------------
+
+fn cmd_checksum(raw: &[String]) -> Result<ExitCode, String> {
+    let args = Args::parse(raw, &["impl", "workload", "dist", "n", "seed", "prefill", "wasm", "out"])?;
+    let kinds = parse_kinds(&args)?;
+    // Zig/WAT/WGSL の run_*_dll(n) と同じ 0..n の末尾追加が既定
+    let workload = parse_workload(&args, "append", 100_000)?;
+    let mut wasm_benches = args
+        .all("wasm")
+        .iter()
+        .map(|spec| wasm::load(spec))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut rows: Vec<(String, &str, Checksum)> = Vec::new();
+    for kind in kinds {
+        let checksum = with_backend!(kind, B => try_workload_checksum::<B>(&workload))
+            .map_err(|e| format!("{}: {}", kind.name(), e))?;
+        rows.push((kind.name().to_string(), Runtime::Native.name(), checksum));
+    }
+    for wasm in &mut wasm_benches {
+        rows.push((wasm.name().to_string(), Runtime::Wasmtime.name(), wasm.checksum(workload.ops)?));
+    }
+
+    // 最初の行を基準にして、全て一致するかを見る
+    let reference = rows.first().map(|row| row.2);
+    let mut matched = true;
+    let mut out = format!("{:<20} {:<9} {:>10} {:>20} {:>10}\n", "implementation", "runtime", "len", "sum", "hash");
+    out.push_str(&"-".repeat(80));
+    out.push('\n');
+    for (name, runtime, checksum) in &rows {
+        let ok = Some(*checksum) == reference;
+        matched &= ok;
+        out.push_str(&format!(
+            "{:<20} {:<9} {:>10} {:>20} {:>#10x}  {}\n",
+            name,
+            runtime,
+            checksum.len,
+            checksum.sum,
+            checksum.hash,
+            if ok { "ok" } else { "MISMATCH" }
+        ));
+    }
+    out.push_str(if matched { "PASS\n" } else { "FAIL\n" });
+    write_output(&args, &out)?;
+    Ok(if matched { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+// --------------------------------------------------------
+// Zig / WAT (.wasm) via wasmtime
+// --------------------------------------------------------
+
+#[cfg(feature = "wasm-runner")]
+mod wasm {
+    use std::path::Path;
+
+    use polyglot_compute_lab::bench::{measure, BenchConfig};
+    use polyglot_compute_lab::report::{BenchmarkReport, Runtime, WorkloadInfo};
+    use polyglot_compute_lab::{Checksum, Workload};
+    use wasmtime::{Engine, Instance, Module, Store, TypedFunc};
+
+    /// コンパイル・インスタンス化済みの `FILE:FUNC`
+    pub struct WasmBench {
+        name: String,
+        label: String,
+        func: String,
+        store: Store<()>,
+        instance: Instance,
+        run: TypedFunc<i32, i32>,
+    }
+
+    /// 計測を始める前に全ての .wasm を読み込んでおく（パスの打ち間違いで長い計測を無駄にしない）
+    pub fn load(spec: &str) -> Result<WasmBench, String> {
+        let (path, func) = spec
+            .rsplit_once(':')
+            .ok_or_else(|| format!("--wasm expects FILE:FUNC, got '{}'", spec))?;
+        let engine = Engine::default();
+        let module = Module::from_file(&engine, path).map_err(|e| format!("{}: {}", path, e))?;
+        let mut store = Store::new(&engine, ());
+        let instance = Instance::new(&mut store, &module, &[]).map_err(|e| format!("{}: {}", path, e))?;
+        let run = instance
+            .get_typed_func::<i32, i32>(&mut store, func)
+            .map_err(|e| format!("{}: function '{}' not found: {}", path, func, e))?;
+        let name = Path::new(path).file_stem().and_then(|s| s.to_str()).unwrap_or(path).to_string();
+        Ok(WasmBench { name, label: spec.to_string(), func: func.to_string(), store, instance, run })
+    }
+
+    impl WasmBench {
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        // FUNC の引数は i32 なので、収まらない N は切り詰めずにエラーにする
+        fn arg(&self, n: usize) -> Result<i32, String> {
+            i32::try_from(n).map_err(|_| format!("{}: --n {} does not fit in the i32 argument", self.label, n))
+        }
+
+        /// `FUNC(n)` を計測する。コンパイルとインスタンス化は計測外
+        /// （Zig/WATは呼び出しごとに自前でヒープをリセットする）。
+        /// 操作列はモジュールが決めるので、使うのは `workload.ops` だけ。
+        pub fn bench(&mut self, workload: &Workload, config: &BenchConfig) -> Result<BenchmarkReport, String> {
+            let n = self.arg(workload.ops)?;
+            let (store, run) = (&mut self.store, &self.run);
+            let mut trap = None;
+            let measurement = measure(config, || {
+                if let Err(e) = run.call(&mut *store, n) {
+                    trap = Some(e.to_string());
+                }
+            });
+            if let Some(e) = trap {
+                return Err(format!("{} trapped: {}", self.label, e));
+            }
+            // checksum_* をエクスポートしていない古いモジュールではチェックサムなし
+            let checksum = self.checksum(workload.ops).ok();
+            let mut report = BenchmarkReport::new(&self.name, Runtime::Wasmtime, workload, &measurement, checksum);
+            // --workload / --dist / --seed / --prefill は FUNC に渡らないので、関数名で区別する
+            report.workload = WorkloadInfo::named(&self.func);
+            // 戻り値に所有権のあるものがないので teardown は意味を持たない
+            report.teardown = None;
+            Ok(report)
+        }
+
+        /// `FUNC(n)` を1回呼び、モジュールの `checksum_len` / `checksum_sum` / `checksum_hash` で
+        /// 結果のリストを読む（zig/dll.zig, zig/zipper.zig, wat/dll.wat が持っている）
+        pub fn checksum(&mut self, n: usize) -> Result<Checksum, String> {
+            let n = self.arg(n)?;
+            let store = &mut self.store;
+            let missing = |e: wasmtime::Error| format!("{}: {} (rebuild the module with build_all.sh)", self.label, e);
+            let len = self.instance.get_typed_func::<(), i32>(&mut *store, "checksum_len").map_err(missing)?;
+            let sum = self.instance.get_typed_func::<(), i64>(&mut *store, "checksum_sum").map_err(missing)?;
+            let hash = self.instance.get_typed_func::<(), i32>(&mut *store, "checksum_hash").map_err(missing)?;
+            let trapped = |e: wasmtime::Error| format!("{} trapped: {}", self.label, e);
+            self.run.call(&mut *store, n).map_err(trapped)?;
+            Ok(Checksum {
+                len: len.call(&mut *store, ()).map_err(trapped)? as u32 as u64,
+                sum: sum.call(&mut *store, ()).map_err(trapped)?,
+                hash: hash.call(&mut *store, ()).map_err(trapped)? as u32,
+            })
+        }
+    }
+}
+
+#[cfg(not(feature = "wasm-runner"))]
+mod wasm {
+    use polyglot_compute_lab::bench::BenchConfig;
+    use polyglot_compute_lab::report::BenchmarkReport;
+    use polyglot_compute_lab::{Checksum, Workload};
+
+    pub enum WasmBench {}
+
+    pub fn load(_spec: &str) -> Result<WasmBench, String> {
+        Err("--wasm needs the wasmtime runner: rebuild with `--features wasm-runner`".to_string())
+    }
+
+    impl WasmBench {
+        pub fn name(&self) -> &str {
+            match *self {}
+        }
+
+        pub fn bench(&mut self, _workload: &Workload, _config: &BenchConfig) -> Result<BenchmarkReport, String> {
+            match *self {}
+        }
+
+        pub fn checksum(&mut self, _n: usize) -> Result<Checksum, String> {
+            match *self {}
+        }
+    }
+}
+
+// --------------------------------------------------------
+// pcl compare
+// --------------------------------------------------------
+
+fn cmd_compare(raw: &[String]) -> Result<ExitCode, String> {
+    let args = Args::parse(raw, &["threshold", "format", "out"])?;
+    let [baseline, candidate] = args.positional.as_slice() else {
+        return Err(format!("compare expects BASELINE and CANDIDATE files\n\n{}", USAGE));
+    };
+    let load = |path: &str| -> Result<Vec<BenchmarkReport>, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        report::from_json(&json).map_err(|e| format!("{}: {}", path, e))
+    };
+    let result = compare(&load(baseline)?, &load(candidate)?, args.number("threshold", 5.0)?);
+    let text = match args.get("format").unwrap_or("table") {
+        "json" => result.to_json() + "\n",
+        "table" => result.to_string() + "\n",
+        other => return Err(format!("unknown format '{}' (expected table or json)", other)),
+    };
+    write_output(&args, &text)?;
+    Ok(if result.passed { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// ========================================================
+// Cross-Runtime Checksum
+// Rust / Zig / WAT / WGSL で同じ値になる、リストの中身の要約
+// ========================================================
+
+// i32の合計はN=100,000で既に溢れるので、合計はi64で持つ。
+// ハッシュはWGSLでも計算できるよう u32 の乗算と加算だけで作る
+// （h = h * PRIME + value mod 2^32。順序が変わると値が変わる）。
+
+/// 要素数・i64の合計・順序つきローリングハッシュ
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Checksum {
+    pub len: u64,
+    pub sum: i64,
+    pub hash: u32,
+}
+
+impl Checksum {
+    /// ハッシュの初期値（FNVのoffset basis）
+    pub const HASH_SEED: u32 = 0x811c_9dc5;
+    /// ハッシュの乗数（FNVのprime）
+    pub const HASH_PRIME: u32 = 0x0100_0193;
+
+    /// 空のリストのチェックサム
+    pub fn new() -> Self {
+        Self { len: 0, sum: 0, hash: Self::HASH_SEED }
+    }
+
+    /// 値を1つ末尾に足す
+    pub fn push(&mut self, value: i32) {
+        self.len += 1;
+        self.sum = self.sum.wrapping_add(i64::from(value));
+        self.hash = self.hash.wrapping_mul(Self::HASH_PRIME).wrapping_add(value as u32);
+    }
+
+    /// 先頭から順に値を流し込む
+    pub fn of<I: IntoIterator<Item = i32>>(values: I) -> Self {
+        let mut checksum = Self::new();
+        for value in values {
+            checksum.push(value);
+        }
+        checksum
+    }
+}
+
+impl Default for Checksum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "len={} sum={} hash={:#010x}", self.len, self.sum, self.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Zig / WAT / JS 側も同じ値を返す（README の 'safe' の例と同じ値）
+    #[test]
+    fn pins_the_value_shared_with_the_other_runtimes() {
+        let checksum = Checksum::of(0..100_000);
+        assert_eq!(checksum, Checksum { len: 100_000, sum: 4_999_950_000, hash: 1_847_792_405 });
+        assert_eq!(Checksum::of([]), Checksum::new());
+        assert_eq!(Checksum::new().hash, Checksum::HASH_SEED);
+    }
+
+    #[test]
+    fn hash_depends_on_the_order_and_wraps_negative_values() {
+        assert_eq!(Checksum::of([1, 2, 3]).hash, 1_924_363_593);
+        assert_eq!(Checksum::of([3, 2, 1]).hash, 3_199_756_825);
+        // 負の値は u32 に読み替えて足す
+        assert_eq!(Checksum::of([-1]), Checksum { len: 1, sum: -1, hash: 84_696_350 });
+    }
+}
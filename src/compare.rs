@@ -0,0 +1,308 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::{BenchmarkReport, Runtime};
+
+// ========================================================
+// Regression Comparison
+// ベースラインと候補のレポートを (実装, ランタイム, ワークロード, N) ごとに突き合わせ、
+// 中央値の変化率と有意性から pass/fail を判定する
+// ========================================================
+
+/// 1項目の判定
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    /// 有意に速くなった
+    Improved,
+    /// 有意差なし、または閾値以内
+    Unchanged,
+    /// 有意に遅くなり、かつ閾値を超えた
+    Regressed,
+    /// 操作後のリストの中身が変わった（速度以前の問題）
+    ChecksumMismatch,
+    /// ベースラインにあって候補にない
+    Missing,
+    /// 候補にだけある
+    New,
+}
+
+impl Status {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Improved => "improved",
+            Status::Unchanged => "unchanged",
+            Status::Regressed => "regressed",
+            Status::ChecksumMismatch => "checksum-mismatch",
+            Status::Missing => "missing",
+            Status::New => "new",
+        }
+    }
+
+    /// ゲートを落とす判定か
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Regressed | Status::ChecksumMismatch | Status::Missing)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 1つの (実装, ランタイム, ワークロード, N) の比較結果。時間は total の中央値 (ms)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    pub implementation: String,
+    pub runtime: Runtime,
+    pub workload: String,
+    pub n: usize,
+    pub baseline_median: Option<f64>,
+    pub candidate_median: Option<f64>,
+    /// (候補 - ベースライン) / ベースライン * 100。正なら遅くなった
+    pub change_pct: Option<f64>,
+    /// 中央値の信頼区間が重ならない
+    pub significant: bool,
+    pub status: Status,
+}
+
+/// 比較全体の結果
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    /// 許容する遅延 (%)
+    pub max_regression_pct: f64,
+    pub passed: bool,
+    pub entries: Vec<Comparison>,
+}
+
+impl ComparisonReport {
+    pub fn failures(&self) -> impl Iterator<Item = &Comparison> {
+        self.entries.iter().filter(|c| c.status.is_failure())
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("comparison serialization")
+    }
+}
+
+impl fmt::Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:<9} {:<24} {:>8} {:>12} {:>12} {:>9}  status",
+            "implementation", "runtime", "workload", "n", "base (ms)", "cand (ms)", "change"
+        )?;
+        let ms = |v: Option<f64>| v.map(|v| format!("{:.3}", v)).unwrap_or_else(|| "-".to_string());
+        for c in &self.entries {
+            let change = c.change_pct.map(|p| format!("{:+.1}%", p)).unwrap_or_else(|| "-".to_string());
+            writeln!(
+                f,
+                "{:<16} {:<9} {:<24} {:>8} {:>12} {:>12} {:>9}  {}{}",
+                c.implementation,
+                c.runtime.name(),
+                c.workload,
+                c.n,
+                ms(c.baseline_median),
+                ms(c.candidate_median),
+                change,
+                c.status,
+                if c.change_pct.is_some() && !c.significant { " (n.s.)" } else { "" },
+            )?;
+        }
+        write!(
+            f,
+            "{} (threshold {:.1}%, {} failure(s))",
+            if self.passed { "PASS" } else { "FAIL" },
+            self.max_regression_pct,
+            self.failures().count()
+        )
+    }
+}
+
+fn same_key(a: &BenchmarkReport, b: &BenchmarkReport) -> bool {
+    a.implementation == b.implementation && a.runtime == b.runtime && a.workload.name == b.workload.name && a.n == b.n
+}
+
+fn compare_one(base: &BenchmarkReport, cand: &BenchmarkReport, max_regression_pct: f64) -> Comparison {
+    let (b, c) = (&base.total, &cand.total);
+    let change_pct = if b.median > 0.0 { Some((c.median - b.median) / b.median * 100.0) } else { None };
+    let slower = c.ci_low > b.ci_high;
+    let faster = c.ci_high < b.ci_low;
+    // 片方にしかチェックサムがない（外部で計測した）ものは比べられない
+    let mismatch = matches!((&base.checksum, &cand.checksum), (Some(b), Some(c)) if b != c);
+    let status = if mismatch {
+        Status::ChecksumMismatch
+    } else if slower && change_pct.is_some_and(|p| p > max_regression_pct) {
+        Status::Regressed
+    } else if faster {
+        Status::Improved
+    } else {
+        Status::Unchanged
+    };
+    Comparison {
+        implementation: base.implementation.clone(),
+        runtime: base.runtime,
+        workload: base.workload.name.clone(),
+        n: base.n,
+        baseline_median: Some(b.median),
+        candidate_median: Some(c.median),
+        change_pct,
+        significant: slower || faster,
+        status,
+    }
+}
+
+fn unmatched(report: &BenchmarkReport, status: Status) -> Comparison {
+    let median = Some(report.total.median);
+    Comparison {
+        implementation: report.implementation.clone(),
+        runtime: report.runtime,
+        workload: report.workload.name.clone(),
+        n: report.n,
+        baseline_median: if status == Status::Missing { median } else { None },
+        candidate_median: if status == Status::New { median } else { None },
+        change_pct: None,
+        significant: false,
+        status,
+    }
+}
+
+/// ベースラインと候補を比較する。
+/// 遅くなった項目は、中央値の信頼区間が重ならず（有意）、かつ変化率が
+/// `max_regression_pct` を超えた場合に `Regressed` になる。
+pub fn compare(baseline: &[BenchmarkReport], candidate: &[BenchmarkReport], max_regression_pct: f64) -> ComparisonReport {
+    let mut entries: Vec<Comparison> = baseline
+        .iter()
+        .map(|base| match candidate.iter().find(|cand| same_key(base, cand)) {
+            Some(cand) => compare_one(base, cand, max_regression_pct),
+            None => unmatched(base, Status::Missing),
+        })
+        .collect();
+    entries.extend(
+        candidate
+            .iter()
+            .filter(|cand| !baseline.iter().any(|base| same_key(base, cand)))
+            .map(|cand| unmatched(cand, Status::New)),
+    );
+    let passed = !entries.iter().any(|c| c.status.is_failure());
+    ComparisonReport { max_regression_pct, passed, entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::Stats;
+    use crate::checksum::Checksum;
+    use crate::report::WorkloadInfo;
+
+    // total の中央値と信頼区間だけを決めたレポート
+    fn report(implementation: &str, median: f64, ci: (f64, f64), checksum: Option<Checksum>) -> BenchmarkReport {
+        let total = Stats { samples: 10, median, ci_low: ci.0, ci_high: ci.1, ..Stats::default() };
+        let mut report = BenchmarkReport::from_samples(
+            implementation,
+            Runtime::Native,
+            1000,
+            WorkloadInfo::named("insert/uniform"),
+            &[],
+            checksum,
+        );
+        report.total = total;
+        report
+    }
+
+    fn compare_pair(base: BenchmarkReport, cand: BenchmarkReport) -> Comparison {
+        let result = compare(&[base], &[cand], 5.0);
+        assert_eq!(result.entries.len(), 1);
+        result.entries.into_iter().next().unwrap()
+    }
+
+    fn checksum_of(values: &[i32]) -> Option<Checksum> {
+        Some(Checksum::of(values.iter().copied()))
+    }
+
+    // 中身は同じで時間だけが違うレポート
+    fn timed(median: f64, ci: (f64, f64)) -> BenchmarkReport {
+        report("bump", median, ci, checksum_of(&[1]))
+    }
+
+    #[test]
+    fn significant_slowdown_over_the_threshold_regresses() {
+        let c = compare_pair(timed(10.0, (9.5, 10.5)), timed(12.0, (11.5, 12.5)));
+        assert_eq!(c.status, Status::Regressed);
+        assert!(c.significant);
+        assert!((c.change_pct.unwrap() - 20.0).abs() < 1e-9);
+        assert!(c.status.is_failure());
+    }
+
+    #[test]
+    fn significant_speedup_improves() {
+        let c = compare_pair(timed(10.0, (9.5, 10.5)), timed(8.0, (7.5, 8.5)));
+        assert_eq!(c.status, Status::Improved);
+        assert!(c.significant);
+        assert!(!c.status.is_failure());
+    }
+
+    #[test]
+    fn overlapping_cis_are_unchanged_even_past_the_threshold() {
+        // 中央値は 20% 遅いが、区間が重なっているので有意ではない
+        let c = compare_pair(timed(10.0, (8.0, 12.5)), timed(12.0, (11.0, 14.0)));
+        assert_eq!(c.status, Status::Unchanged);
+        assert!(!c.significant);
+        // 区間の端が接しているだけでも重なりとみなす
+        let c = compare_pair(timed(10.0, (9.0, 11.0)), timed(12.0, (11.0, 13.0)));
+        assert_eq!(c.status, Status::Unchanged);
+        assert!(!c.significant);
+    }
+
+    #[test]
+    fn significant_slowdown_within_the_threshold_is_unchanged() {
+        let c = compare_pair(timed(10.0, (9.9, 10.1)), timed(10.3, (10.2, 10.4)));
+        assert_eq!(c.status, Status::Unchanged);
+        assert!(c.significant);
+    }
+
+    #[test]
+    fn different_final_lists_are_a_checksum_mismatch() {
+        // 時間が同じでも中身が違えば落とす（append/insert でも合計以外の len/hash で検出できる）
+        let c = compare_pair(
+            report("bump", 10.0, (9.5, 10.5), checksum_of(&[1, 2])),
+            report("bump", 10.0, (9.5, 10.5), checksum_of(&[2, 1])),
+        );
+        assert_eq!(c.status, Status::ChecksumMismatch);
+        assert!(c.status.is_failure());
+        // 片方にチェックサムがなければ比べない
+        let c = compare_pair(report("zig", 10.0, (9.5, 10.5), None), report("zig", 10.0, (9.5, 10.5), checksum_of(&[1])));
+        assert_eq!(c.status, Status::Unchanged);
+    }
+
+    #[test]
+    fn unmatched_entries_are_missing_or_new() {
+        let result = compare(
+            &[report("bump", 10.0, (9.5, 10.5), None)],
+            &[report("safe", 10.0, (9.5, 10.5), None)],
+            5.0,
+        );
+        let statuses: Vec<_> = result.entries.iter().map(|c| (c.implementation.as_str(), c.status)).collect();
+        assert_eq!(statuses, [("bump", Status::Missing), ("safe", Status::New)]);
+        assert_eq!(result.entries[0].baseline_median, Some(10.0));
+        assert_eq!(result.entries[1].candidate_median, Some(10.0));
+        assert!(!result.passed);
+        assert_eq!(result.failures().count(), 1);
+    }
+
+    #[test]
+    fn gate_passes_only_without_failures() {
+        let safe = |median, ci, checksum| report("safe", median, ci, checksum);
+        let base = [timed(10.0, (9.5, 10.5)), safe(20.0, (19.0, 21.0), checksum_of(&[1]))];
+        let cand = [timed(8.0, (7.5, 8.5)), safe(20.5, (19.5, 21.5), checksum_of(&[1]))];
+        assert!(compare(&base, &cand, 5.0).passed);
+        assert!(compare(&base, &base, 5.0).passed);
+
+        let cand = [cand[0].clone(), safe(20.5, (19.5, 21.5), checksum_of(&[2]))];
+        let result = compare(&base, &cand, 5.0);
+        assert!(!result.passed);
+        assert_eq!(result.failures().map(|c| c.status).collect::<Vec<_>>(), [Status::ChecksumMismatch]);
+    }
+}
@@ -0,0 +1,364 @@
+use std::fmt;
+
+use crate::checksum::Checksum;
+use crate::sequence::DynSequence;
+use crate::trace::Trace;
+use crate::workload::{Op, Workload};
+use crate::BackendKind;
+
+// ========================================================
+// Differential Checker
+// 同じ操作列を全バックエンドと Vec<i32>（オラクル）に流し、
+// 1操作ごとに結果と全要素を突き合わせる
+// ========================================================
+
+/// 仕様の基準となる実装。`SequenceBackend` と同じ範囲外の扱いをする
+/// (insert は末尾に追加、remove/get は `None`)。
+#[derive(Clone, Debug, Default)]
+pub struct Oracle(pub Vec<i32>);
+
+impl DynSequence for Oracle {
+    fn append(&mut self, value: i32) {
+        self.0.push(value);
+    }
+
+    fn insert(&mut self, index: usize, value: i32) {
+        if index >= self.0.len() {
+            self.0.push(value);
+        } else {
+            self.0.insert(index, value);
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> Option<i32> {
+        if index < self.0.len() {
+            Some(self.0.remove(index))
+        } else {
+            None
+        }
+    }
+
+    fn pop_front(&mut self) -> Option<i32> {
+        self.remove(0)
+    }
+
+    fn pop_back(&mut self) -> Option<i32> {
+        self.0.pop()
+    }
+
+    fn get(&self, index: usize) -> Option<i32> {
+        self.0.get(index).copied()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn sum(&self) -> i32 {
+        self.0.iter().fold(0i32, |acc, v| acc.wrapping_add(*v))
+    }
+
+    fn sum_i64(&self) -> i64 {
+        self.0.iter().map(|&v| i64::from(v)).sum()
+    }
+
+    fn sum_i128(&self) -> i128 {
+        self.0.iter().map(|&v| i128::from(v)).sum()
+    }
+
+    fn checksum(&self) -> Checksum {
+        Checksum::of(self.0.iter().copied())
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn to_vec(&self) -> Vec<i32> {
+        self.0.clone()
+    }
+}
+
+/// オラクルと食い違った最初の操作
+#[derive(Clone, Debug)]
+pub struct Divergence {
+    pub backend: BackendKind,
+    /// 食い違った操作の位置（0始まり）
+    pub step: usize,
+    pub op: Op,
+    pub expected_result: Option<i64>,
+    pub actual_result: Option<i64>,
+    pub expected: Vec<i32>,
+    pub actual: Vec<i32>,
+    /// 同じバックエンドで再現する最小化済みの操作列
+    pub reproducer: Trace,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "backend '{}' diverged from Vec<i32> at op #{}: {:?}",
+            self.backend.name(),
+            self.step,
+            self.op
+        )?;
+        writeln!(f, "  result   expected {:?}, got {:?}", self.expected_result, self.actual_result)?;
+        writeln!(f, "  contents expected {}", preview(&self.expected))?;
+        writeln!(f, "           got      {}", preview(&self.actual))?;
+        writeln!(f, "minimized reproducer ({} ops):", self.reproducer.len())?;
+        write!(f, "{}", self.reproducer.to_text())
+    }
+}
+
+// 長いリストは先頭と末尾だけ表示する
+fn preview(values: &[i32]) -> String {
+    if values.len() <= 16 {
+        format!("{:?}", values)
+    } else {
+        format!(
+            "{:?} ... {:?} (len {})",
+            &values[..8],
+            &values[values.len() - 8..],
+            values.len()
+        )
+    }
+}
+
+// 1ステップ分のずれ（最小化の判定にも使う）
+struct Mismatch {
+    step: usize,
+    expected_result: Option<i64>,
+    actual_result: Option<i64>,
+    expected: Vec<i32>,
+    actual: Vec<i32>,
+}
+
+// 検査するリストは `new_list` で毎回作り直す（バックエンド以外の実装もテストできるように）
+type NewList<'a> = &'a dyn Fn() -> Box<dyn DynSequence>;
+
+fn first_mismatch(new_list: NewList<'_>, ops: &[Op]) -> Option<Mismatch> {
+    let mut oracle = Oracle::default();
+    let mut list = new_list();
+    for (step, op) in ops.iter().enumerate() {
+        let expected_result = op.apply(&mut oracle);
+        let actual_result = op.apply(&mut *list);
+        let actual = list.to_vec();
+        if expected_result != actual_result || actual != oracle.0 || list.len() != oracle.len() {
+            return Some(Mismatch {
+                step,
+                expected_result,
+                actual_result,
+                expected: oracle.0,
+                actual,
+            });
+        }
+    }
+    None
+}
+
+/// ずれが再現する限り操作を取り除いていく (ddmin の簡易版)。
+/// 結果は 1-minimal（どの1操作を取り除いてもずれが再現しない）
+fn minimize(new_list: NewList<'_>, ops: &[Op]) -> Vec<Op> {
+    let mut current = ops.to_vec();
+    let mut chunk = current.len() / 2;
+    while chunk >= 1 {
+        let mut removed = false;
+        let mut start = 0;
+        while start < current.len() {
+            let end = (start + chunk).min(current.len());
+            let mut candidate = current[..start].to_vec();
+            candidate.extend_from_slice(&current[end..]);
+            if let Some(m) = first_mismatch(new_list, &candidate) {
+                candidate.truncate(m.step + 1);
+                current = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+        // 後ろを取り除いたことで前の操作も要らなくなっていることがあるので、
+        // 1操作ずつの段は何も取り除けなくなるまで繰り返す
+        if chunk > 1 || !removed {
+            chunk /= 2;
+        }
+    }
+    current
+}
+
+/// 1つのバックエンドを検査する
+pub fn check_backend(kind: BackendKind, ops: &[Op]) -> Result<(), Box<Divergence>> {
+    let new_list = || kind.boxed();
+    let m = match first_mismatch(&new_list, ops) {
+        Some(m) => m,
+        None => return Ok(()),
+    };
+    let reproducer = Trace { ops: minimize(&new_list, &ops[..=m.step]) };
+    Err(Box::new(Divergence {
+        backend: kind,
+        step: m.step,
+        op: ops[m.step],
+        expected_result: m.expected_result,
+        actual_result: m.actual_result,
+        expected: m.expected,
+        actual: m.actual,
+        reproducer,
+    }))
+}
+
+/// 全バックエンドを検査し、食い違ったものを全て返す（空なら全て一致）
+pub fn check_ops(ops: &[Op]) -> Vec<Divergence> {
+    BackendKind::ALL
+        .iter()
+        .filter_map(|kind| check_backend(*kind, ops).err().map(|d| *d))
+        .collect()
+}
+
+/// `BackendKind` にない設定（ブロックサイズなど）で作ったリストを検査する。
+/// 食い違ったら最小化した操作列とともに panic する
+#[cfg(test)]
+pub(crate) fn assert_matches_oracle(name: &str, new_list: NewList<'_>, ops: &[Op]) {
+    if let Some(m) = first_mismatch(new_list, ops) {
+        let reproducer = Trace { ops: minimize(new_list, &ops[..=m.step]) };
+        panic!(
+            "{} diverged from Vec<i32> at op #{}: {:?}\n  result   expected {:?}, got {:?}\n  \
+             contents expected {}\n           got      {}\nminimized reproducer ({} ops):\n{}",
+            name,
+            m.step,
+            ops[m.step],
+            m.expected_result,
+            m.actual_result,
+            preview(&m.expected),
+            preview(&m.actual),
+            reproducer.len(),
+            reproducer.to_text()
+        );
+    }
+}
+
+pub fn check_workload(workload: &Workload) -> Vec<Divergence> {
+    check_ops(&workload.generate())
+}
+
+pub fn check_trace(trace: &Trace) -> Vec<Divergence> {
+    check_ops(&trace.ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workload::{OpMix, PositionDist};
+
+    // 3要素以上あるときの先頭への挿入だけを2番目に入れてしまう、わざと壊したリスト
+    #[derive(Default)]
+    struct Buggy(Oracle);
+
+    impl DynSequence for Buggy {
+        fn append(&mut self, value: i32) {
+            self.0.append(value);
+        }
+
+        fn insert(&mut self, index: usize, value: i32) {
+            let index = if index == 0 && self.0.len() >= 3 { 1 } else { index };
+            self.0.insert(index, value);
+        }
+
+        fn remove(&mut self, index: usize) -> Option<i32> {
+            self.0.remove(index)
+        }
+
+        fn pop_front(&mut self) -> Option<i32> {
+            self.0.pop_front()
+        }
+
+        fn pop_back(&mut self) -> Option<i32> {
+            self.0.pop_back()
+        }
+
+        fn get(&self, index: usize) -> Option<i32> {
+            self.0.get(index)
+        }
+
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        fn is_empty(&self) -> bool {
+            self.0.is_empty()
+        }
+
+        fn sum(&self) -> i32 {
+            self.0.sum()
+        }
+
+        fn sum_i64(&self) -> i64 {
+            self.0.sum_i64()
+        }
+
+        fn sum_i128(&self) -> i128 {
+            self.0.sum_i128()
+        }
+
+        fn checksum(&self) -> Checksum {
+            self.0.checksum()
+        }
+
+        fn clear(&mut self) {
+            self.0.clear();
+        }
+
+        fn to_vec(&self) -> Vec<i32> {
+            self.0.to_vec()
+        }
+    }
+
+    fn new_buggy() -> Box<dyn DynSequence> {
+        Box::<Buggy>::default()
+    }
+
+    #[test]
+    fn planted_bug_is_found_and_minimized_to_a_1_minimal_reproducer() {
+        let ops = Workload::new(400, 11, OpMix::EDITOR, PositionDist::FrontBiased).with_prefill(5).generate();
+        let m = first_mismatch(&new_buggy, &ops).expect("the planted bug diverges");
+        let reproducer = minimize(&new_buggy, &ops[..=m.step]);
+
+        assert!(first_mismatch(&new_buggy, &reproducer).is_some());
+        for i in 0..reproducer.len() {
+            let mut without = reproducer.clone();
+            without.remove(i);
+            assert!(
+                first_mismatch(&new_buggy, &without).is_none(),
+                "removing op #{} of {:?} still diverges",
+                i,
+                reproducer
+            );
+        }
+        // 3要素を作って先頭へ挿入する、が最小
+        assert_eq!(reproducer.len(), 4, "{:?}", reproducer);
+        assert!(matches!(reproducer[3], Op::Insert(0, _)));
+    }
+
+    #[test]
+    fn oracle_agrees_with_itself() {
+        let ops = Workload::new(300, 5, OpMix::EDITOR, PositionDist::Uniform).generate();
+        assert!(first_mismatch(&|| Box::<Oracle>::default(), &ops).is_none());
+    }
+
+    #[test]
+    fn every_backend_matches_the_oracle() {
+        let mix = OpMix { append: 2, insert: 4, remove: 3, get: 1, sum: 1 };
+        for dist in [PositionDist::Uniform, PositionDist::Head, PositionDist::Tail, PositionDist::Sequential(4)] {
+            let ops = Workload::new(600, 123456789, mix, dist).with_prefill(50).generate();
+            for &kind in BackendKind::ALL {
+                if let Err(d) = check_backend(kind, &ops) {
+                    panic!("{}", d);
+                }
+            }
+            assert!(check_ops(&ops).is_empty());
+        }
+    }
+}
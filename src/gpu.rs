@@ -0,0 +1,177 @@
+// ========================================================
+// WGPU (WebGPU) Core Implementation
+// Impl: GPU Compute Shader with Atomic Bump Allocator
+// ========================================================
+
+use crate::checksum::Checksum;
+
+// 共通ロジック: WASM依存もPython依存もしない純粋な非同期関数
+// 戻り値はi64の合計（失敗時は -1.0）
+pub async fn run_wgpu_core(iterations: u32) -> f64 {
+    match run_wgpu_checksum(iterations).await {
+        Some(checksum) => checksum.sum as f64,
+        None => -1.0,
+    }
+}
+
+/// GPU上で 0..iterations を追加したリストのチェックサム（Rust/Zig/WATと同じ値になる）
+pub async fn run_wgpu_checksum(iterations: u32) -> Option<Checksum> {
+    // ------------------------------------------------------------
+    // 1. Initialize WGPU (Adapter & Device)
+    // ------------------------------------------------------------
+    let instance = wgpu::Instance::default();
+    
+    // Native(Python)とWebでAdapterの取得戦略が少し違うが、
+    // default() で大抵うまくいく（NativeならVulkan/Metal/DX12が選ばれる）
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions::default())
+        .await
+        .expect("Failed to find an appropriate adapter");
+
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        )
+        .await
+        .expect("Failed to create device");
+
+    // ------------------------------------------------------------
+    // 2. WGSL Shader (External File)
+    // ------------------------------------------------------------
+    // コンパイル時にファイルを文字列として読み込む
+    let shader_raw = include_str!("shader.wgsl");
+    // プレースホルダーを実際の数値（文字列）に置換する
+    // 例: "ITERATIONS_PLACEHOLDER" -> "100000u"
+    let shader_source = shader_raw.replace(
+        "ITERATIONS_PLACEHOLDER", 
+        &format!("{}u", iterations)
+    );
+
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("DLL Shader"),
+        source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+    });
+
+    // ------------------------------------------------------------
+    // 3. Setup Buffers
+    // ------------------------------------------------------------
+    use wgpu::util::DeviceExt; // for create_buffer_init
+
+    let heap_size = 128 * 1024 * 1024; 
+    let buffer_heap = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Heap Buffer"),
+        size: heap_size,
+        usage: wgpu::BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+
+    let buffer_alloc = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Alloc Buffer"),
+        contents: bytemuck::cast_slice(&[1u32]),
+        usage: wgpu::BufferUsages::STORAGE,
+    });
+
+    let buffer_head_tail = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("HeadTail Buffer"),
+        contents: bytemuck::cast_slice(&[0u32, 0u32]), 
+        usage: wgpu::BufferUsages::STORAGE,
+    });
+
+    // Result { len, sum_lo, sum_hi, hash } (u32 x 4)
+    let buffer_result = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Result Buffer"),
+        size: 16,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+
+    let buffer_staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Staging Buffer"),
+        size: 16,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    // ------------------------------------------------------------
+    // 4. Pipeline & Execute
+    // ------------------------------------------------------------
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[
+            wgpu::BindGroupLayoutEntry { binding: 0, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Storage { read_only: false }, has_dynamic_offset: false, min_binding_size: None }, count: None },
+            wgpu::BindGroupLayoutEntry { binding: 1, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Storage { read_only: false }, has_dynamic_offset: false, min_binding_size: None }, count: None },
+            wgpu::BindGroupLayoutEntry { binding: 2, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Storage { read_only: false }, has_dynamic_offset: false, min_binding_size: None }, count: None },
+            wgpu::BindGroupLayoutEntry { binding: 3, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Storage { read_only: false }, has_dynamic_offset: false, min_binding_size: None }, count: None },
+        ],
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: None,
+        layout: Some(&pipeline_layout),
+        module: &shader,
+        entry_point: Some("main"),
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+        cache: None,
+    });
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &bind_group_layout,
+        entries: &[
+            wgpu::BindGroupEntry { binding: 0, resource: buffer_heap.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 1, resource: buffer_alloc.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 2, resource: buffer_head_tail.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 3, resource: buffer_result.as_entire_binding() },
+        ],
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: None, timestamp_writes: None });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(1, 1, 1);
+    }
+    
+    encoder.copy_buffer_to_buffer(&buffer_result, 0, &buffer_staging, 0, 16);
+    queue.submit(Some(encoder.finish()));
+
+    // ------------------------------------------------------------
+    // 5. Read Back (Async)
+    // ------------------------------------------------------------
+    let buffer_slice = buffer_staging.slice(..);
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result: Result<(), wgpu::BufferAsyncError>| {
+        sender.send(result).unwrap();
+    });
+    
+    device.poll(wgpu::Maintain::Wait);
+
+    if let Ok(Ok(())) = receiver.await {
+        let data = buffer_slice.get_mapped_range();
+        let [len, sum_lo, sum_hi, hash]: [u32; 4] = *bytemuck::from_bytes(&data[..]);
+        drop(data);
+        buffer_staging.unmap();
+        // WGSLにはi64が無いので、合計は上位/下位の32bitに分けて返ってくる
+        return Some(Checksum {
+            len: u64::from(len),
+            sum: ((u64::from(sum_hi) << 32) | u64::from(sum_lo)) as i64,
+            hash,
+        });
+    }
+
+    None
+}
@@ -0,0 +1,112 @@
+use std::iter::Sum;
+
+use crate::checksum::Checksum;
+use crate::workload::Workload;
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Generic Harness
+// ========================================================
+
+/// 0..iterations を末尾に追加してから合計を返す（Sumベンチマーク）
+/// 合計はi64（i32だとN=100,000で溢れる）
+pub fn run_append_sum<B: SequenceBackend>(iterations: i32) -> i64 {
+    run_append_sum_on(B::with_capacity(iterations.max(0) as usize), iterations)
+}
+
+/// 作成済みのリストで `run_append_sum` を実行する（ブロックサイズなどを指定したい実装用）
+pub fn run_append_sum_on<B: SequenceBackend>(mut list: B, iterations: i32) -> i64 {
+    for i in 0..iterations {
+        list.append(i);
+    }
+    list.sum_i64()
+    // list はここでDrop（解放コストも計測に含める）
+}
+
+/// `run_append_sum` の確保失敗を返す版（固定容量の `BumpDll` をPython/JSから呼ぶ用）
+pub fn try_run_append_sum<B: SequenceBackend>(iterations: i32) -> Result<i64, AllocError> {
+    let mut list = B::with_capacity(iterations.max(0) as usize);
+    for i in 0..iterations {
+        list.try_append(i)?;
+    }
+    Ok(list.sum_i64())
+}
+
+/// `values` を末尾に追加してから合計を返す（要素型を選べるSum。Python/JSの型別エクスポート用）
+pub fn try_append_sum<T, B>(values: &[T]) -> Result<T, AllocError>
+where
+    T: Clone + Sum,
+    B: SequenceBackend<T>,
+{
+    let mut list = B::with_capacity(values.len());
+    for value in values {
+        list.try_append(value.clone())?;
+    }
+    Ok(list.sum())
+}
+
+/// `try_append_sum` の i32 版。合計は i64 で返す（i32 ではN=100,000で溢れる）
+pub fn try_append_sum_i64<T, B>(values: &[T]) -> Result<i64, AllocError>
+where
+    T: Clone + Into<i64>,
+    B: SequenceBackend<T>,
+{
+    let mut list = B::with_capacity(values.len());
+    for value in values {
+        list.try_append(value.clone())?;
+    }
+    Ok(list.sum_i64())
+}
+
+/// `try_append_sum` の i64 版。合計は i128 で取るので途中で溢れない
+pub fn try_append_sum_i128<T, B>(values: &[T]) -> Result<i128, AllocError>
+where
+    T: Clone + Into<i128>,
+    B: SequenceBackend<T>,
+{
+    let mut list = B::with_capacity(values.len());
+    for value in values {
+        list.try_append(value.clone())?;
+    }
+    Ok(list.sum_i128())
+}
+
+/// 線形合同法で決めた位置へ0..iterationsを挿入する（Random Insertベンチマーク）
+/// 操作列は `Workload::random_insert` が生成する（Zig/Python版と同じ位置列）
+pub fn run_random_insert<B: SequenceBackend>(iterations: i32) {
+    run_workload::<B>(&Workload::random_insert(iterations.max(0) as usize));
+}
+
+/// 作成済みのリストで `run_random_insert` を実行する
+pub fn run_random_insert_on<B: SequenceBackend>(mut list: B, iterations: i32) {
+    Workload::random_insert(iterations.max(0) as usize).run(&mut list);
+}
+
+/// 新しいリストに `workload` の操作列を流し込み、結果のチェックサムを返す
+pub fn run_workload<B: SequenceBackend>(workload: &Workload) -> i64 {
+    let mut list = B::with_capacity(workload.prefill + workload.ops);
+    workload.run(&mut list)
+}
+
+pub fn try_run_workload<B: SequenceBackend>(workload: &Workload) -> Result<i64, AllocError> {
+    let mut list = B::with_capacity(workload.prefill + workload.ops);
+    workload.try_run(&mut list)
+}
+
+/// `workload` を流し込んだ後のリストのチェックサム（他のランタイムの結果と突き合わせる用）
+/// 例: `Workload::new(n, 0, OpMix::APPEND, ..)` は Zig/WAT/WGSL の `run_*_dll(n)` と同じリストになる
+pub fn try_workload_checksum<B: SequenceBackend>(workload: &Workload) -> Result<Checksum, AllocError> {
+    let mut list = B::with_capacity(workload.prefill + workload.ops);
+    workload.try_run(&mut list)?;
+    Ok(list.checksum())
+}
+
+/// `from_slice` で一括構築して合計を返す（Python のバッファ・JS の `Int32Array` から呼ぶ用）
+pub fn try_from_slice_sum_i64<B: SequenceBackend>(values: &[i32]) -> Result<i64, AllocError> {
+    Ok(B::try_from_slice(values)?.sum_i64())
+}
+
+/// `from_slice` で一括構築したリストのチェックサム（1つずつ append した結果と同じになる）
+pub fn try_from_slice_checksum<B: SequenceBackend>(values: &[i32]) -> Result<Checksum, AllocError> {
+    Ok(B::try_from_slice(values)?.checksum())
+}
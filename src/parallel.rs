@@ -0,0 +1,242 @@
+use rayon::prelude::*;
+use rayon::ThreadPoolBuildError;
+
+use crate::checksum::Checksum;
+use crate::{SequenceBackend, SoaBumpDll, UnsafeZipperList, ZipperList};
+
+// ========================================================
+// Parallel (rayon)
+// 多コアでの合計・一括構築・リストランキング
+// ========================================================
+
+// 連続した配列を持つ実装（Zipper系）は配列を分割して各スレッドで足すだけ。
+// リンクを辿る実装は順に辿ると並列にならないので、添字で繋いだ実装（SoaBumpDll）の
+// next 配列に対してポインタジャンプ（Wyllie のリストランキング）で各ノードの位置を求め、
+// 位置に書き込んで並べ直す。
+
+// 添字の「無し」（各バックエンドの NIL と同じ）
+const NIL: u32 = u32::MAX;
+
+/// `threads` 本のスレッドプールの中で `f` を実行する（`threads == 0` なら論理コア数）。
+/// `par_*` メソッドは呼ばれたプールのスレッド数で動く。
+pub fn with_threads<R, F>(threads: usize, f: F) -> Result<R, ThreadPoolBuildError>
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build()?;
+    Ok(pool.install(f))
+}
+
+/// 各ノードから鎖の末尾までの距離を求める（Wyllie のポインタジャンプ、O(N log N) の仕事量）。
+///
+/// `succ[i]` はノード `i` の次のノードの添字で、末尾は `u32::MAX`。鎖は複数あってよい。
+/// 1ラウンドごとに飛ぶ距離が倍になるので、ラウンド数は一番長い鎖の長さの log2。
+pub fn list_rank(succ: &[u32]) -> Vec<u32> {
+    let mut rank: Vec<u32> = succ.par_iter().map(|&next| u32::from(next != NIL)).collect();
+    let mut jump = succ.to_vec();
+    let mut next_rank = vec![0; succ.len()];
+    let mut next_jump = vec![NIL; succ.len()];
+    while jump.par_iter().any(|&next| next != NIL) {
+        next_rank.par_iter_mut().zip(next_jump.par_iter_mut()).enumerate().for_each(|(i, (r, j))| {
+            match jump[i] {
+                NIL => {
+                    *r = rank[i];
+                    *j = NIL;
+                }
+                next => {
+                    // 飛び先までの距離を足し、飛び先の飛び先へ繋ぎ変える
+                    *r = rank[i] + rank[next as usize];
+                    *j = jump[next as usize];
+                }
+            }
+        });
+        std::mem::swap(&mut rank, &mut next_rank);
+        std::mem::swap(&mut jump, &mut next_jump);
+    }
+    rank
+}
+
+// 複数のスレッドから重ならない位置へ書き込むためのポインタ
+#[derive(Clone, Copy)]
+struct SharedOut<T>(*mut T);
+
+unsafe impl<T: Send> Send for SharedOut<T> {}
+unsafe impl<T: Send> Sync for SharedOut<T> {}
+
+impl<T> SharedOut<T> {
+    // フィールドを直接使うとクロージャが生ポインタだけをキャプチャして Send でなくなるので、メソッド越しに使う
+    unsafe fn write(self, index: usize, value: T) {
+        self.0.add(index).write(value);
+    }
+}
+
+/// `live` なノード `i` の値を位置 `len - 1 - rank[i]` に並べる（`list_rank` の結果を先頭からの順にする）。
+/// 生きているノードの rank が 0..len を1回ずつ取ること（そうでないと範囲外や未初期化の要素が残る）。
+pub(crate) unsafe fn scatter_by_rank<T: Copy + Send + Sync>(
+    values: &[T],
+    rank: &[u32],
+    live: &[bool],
+    len: usize,
+) -> Vec<T> {
+    let mut out: Vec<T> = Vec::with_capacity(len);
+    let base = SharedOut(out.as_mut_ptr());
+    values.par_iter().zip(rank).zip(live).for_each(|((&value, &rank), &live)| {
+        if live {
+            // rank は生きているノードの間で重複しないので、各位置に1回だけ書く
+            base.write(len - 1 - rank as usize, value);
+        }
+    });
+    // 0..len のすべての位置が書き込み済み
+    out.set_len(len);
+    out
+}
+
+// 0..iterations の入力（一括構築の元データ）
+fn input(iterations: i32) -> Vec<i32> {
+    (0..iterations.max(0)).into_par_iter().collect()
+}
+
+/// `ZipperList::par_from_slice` で作って `par_sum_i64` で足す（`run_append_sum` の並列版）
+pub fn run_par_zipper_sum(iterations: i32) -> i64 {
+    ZipperList::par_from_slice(&input(iterations)).par_sum_i64()
+}
+
+/// `UnsafeZipperList::par_from_slice` で作って `par_sum_i64` で足す
+pub fn run_par_unsafe_zipper_sum(iterations: i32) -> i64 {
+    UnsafeZipperList::par_from_slice(&input(iterations)).par_sum_i64()
+}
+
+/// スロットの順番と位置が食い違う `SoaBumpDll` を作り、`par_to_vec`（リストランキング）で並べ直す。
+/// 偶数は末尾、奇数は先頭に足すので、先頭からの順は [.., 3, 1, 0, 2, 4, ..] になる。
+pub fn run_par_list_rank(iterations: i32) -> Checksum {
+    let mut list = SoaBumpDll::with_capacity(iterations.max(0) as usize);
+    for i in 0..iterations {
+        if i % 2 == 0 {
+            list.append(i);
+        } else {
+            list.insert(0, i);
+        }
+    }
+    Checksum::of(list.par_to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0..n を決まった乱数で並べ替えた順（Fisher-Yates）
+    fn shuffled(n: usize, seed: u64) -> Vec<u32> {
+        let mut order: Vec<u32> = (0..n as u32).collect();
+        let mut rng = seed.max(1);
+        for i in (1..n).rev() {
+            rng ^= rng << 13;
+            rng ^= rng >> 7;
+            rng ^= rng << 17;
+            order.swap(i, (rng % (i as u64 + 1)) as usize);
+        }
+        order
+    }
+
+    // order の順に繋いだ succ 配列
+    fn chain(order: &[u32], n: usize) -> Vec<u32> {
+        let mut succ = vec![NIL; n];
+        for pair in order.windows(2) {
+            succ[pair[0] as usize] = pair[1];
+        }
+        succ
+    }
+
+    // 各ノードから末尾まで1つずつ辿って数える
+    fn sequential_rank(succ: &[u32]) -> Vec<u32> {
+        (0..succ.len())
+            .map(|start| {
+                let (mut current, mut rank) = (succ[start], 0);
+                while current != NIL {
+                    current = succ[current as usize];
+                    rank += 1;
+                }
+                rank
+            })
+            .collect()
+    }
+
+    #[test]
+    fn list_rank_of_a_shuffled_chain_matches_a_sequential_walk() {
+        for (n, seed) in [(2, 1), (7, 2), (100, 3), (1000, 4), (4097, 5)] {
+            let order = shuffled(n, seed);
+            let succ = chain(&order, n);
+            let rank = list_rank(&succ);
+            assert_eq!(rank, sequential_rank(&succ), "n = {}", n);
+            // 先頭は n - 1、末尾は 0
+            assert_eq!(rank[order[0] as usize], n as u32 - 1);
+            assert_eq!(rank[order[n - 1] as usize], 0);
+        }
+    }
+
+    #[test]
+    fn list_rank_handles_several_chains() {
+        let order = shuffled(300, 9);
+        // 3本の鎖に切る
+        let mut succ = vec![NIL; 300];
+        for part in order.chunks(100) {
+            for pair in part.windows(2) {
+                succ[pair[0] as usize] = pair[1];
+            }
+        }
+        assert_eq!(list_rank(&succ), sequential_rank(&succ));
+    }
+
+    #[test]
+    fn list_rank_of_empty_and_single_node() {
+        assert_eq!(list_rank(&[]), Vec::<u32>::new());
+        assert_eq!(list_rank(&[NIL]), [0]);
+        assert_eq!(list_rank(&[NIL, NIL]), [0, 0]);
+    }
+
+    #[test]
+    fn scatter_by_rank_skips_dead_slots() {
+        // スロット 0, 2, 3 が 3 -> 0 -> 2 の順で繋がり、1 は使っていない
+        let values = [10, 99, 20, 30];
+        let succ = [2, NIL, NIL, 0];
+        let live = [true, false, true, true];
+        let rank = list_rank(&succ);
+        assert_eq!(unsafe { scatter_by_rank(&values, &rank, &live, 3) }, [30, 10, 20]);
+        assert!(unsafe { scatter_by_rank::<i32>(&[], &[], &[], 0) }.is_empty());
+    }
+
+    #[test]
+    fn one_and_four_threads_give_identical_results() {
+        let succ = chain(&shuffled(5000, 11), 5000);
+        let mut list = SoaBumpDll::new();
+        for i in 0..3000 {
+            if i % 3 == 0 {
+                list.insert(list.len() / 2, i);
+            } else {
+                list.append(i);
+            }
+        }
+        list.remove(10);
+        list.remove(1234);
+        assert!(!list.is_in_order());
+        let expected: Vec<i32> = list.iter().collect();
+
+        let run = |threads| {
+            with_threads(threads, || (list_rank(&succ), list.par_to_vec(), list.par_sum_i64(), run_par_list_rank(1001)))
+                .expect("thread pool")
+        };
+        let (one, four) = (run(1), run(4));
+        assert_eq!(one, four);
+        assert_eq!(one.0, sequential_rank(&succ));
+        assert_eq!(one.1, expected);
+        assert_eq!(one.2, expected.iter().map(|&v| i64::from(v)).sum::<i64>());
+    }
+
+    #[test]
+    fn run_par_list_rank_puts_odds_first_in_reverse() {
+        let expected = [5, 3, 1, 0, 2, 4];
+        assert_eq!(run_par_list_rank(6), Checksum::of(expected));
+        assert_eq!(run_par_list_rank(0), Checksum::new());
+        assert_eq!(run_par_list_rank(1), Checksum::of([0]));
+    }
+}
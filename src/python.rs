@@ -0,0 +1,1113 @@
+// --------------------------------------------------------
+// Python Interface
+// --------------------------------------------------------
+// pyo3 0.20 のマクロ展開が新しいrustc/clippyの lint に引っかかるので抑制する
+#![allow(non_local_definitions, clippy::unsafe_removed_from_name)]
+
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict, PyList, PySequence, PySlice};
+
+use crate::bench::{bench, BenchConfig};
+use crate::compare::compare;
+use crate::gpu::{run_wgpu_checksum, run_wgpu_core};
+use crate::checksum::Checksum;
+use crate::harness::{
+    run_append_sum, run_append_sum_on, run_random_insert, run_random_insert_on, try_append_sum, try_append_sum_i128,
+    try_append_sum_i64, try_from_slice_checksum, try_from_slice_sum_i64, try_run_append_sum, try_run_workload,
+    try_workload_checksum,
+};
+use crate::diff::{check_ops, Divergence};
+use crate::parallel::{run_par_list_rank, run_par_unsafe_zipper_sum, run_par_zipper_sum, with_threads};
+use crate::report::{self, BenchmarkReport, Runtime, WorkloadInfo};
+use crate::sequence::DynSequence;
+use crate::trace::{Trace, TraceError};
+use crate::workload::{Op, Workload};
+use crate::backends::{DEFAULT_BLOCK_SIZE, DEFAULT_LEVEL_SEED};
+use crate::{
+    with_backend, AllocError, ArenaDll, BackendKind, BumpDll, DoublyLinkedList, GapBuffer, GrowableBumpDll, SkipList, SoaBumpDll, TreapList, UnrolledList,
+    UnsafeDll, UnsafeZipperList, ZipperList,
+};
+
+// アリーナの容量不足は MemoryError にする（panicさせるとプロセスごとabortする）
+impl From<AllocError> for PyErr {
+    fn from(e: AllocError) -> Self {
+        PyErr::new::<pyo3::exceptions::PyMemoryError, _>(e.to_string())
+    }
+}
+
+#[pyfunction]
+fn run_rust_dll_py(iterations: i32) -> PyResult<i64> {
+    Ok(run_append_sum::<DoublyLinkedList>(iterations))
+}
+
+#[pyfunction]
+fn run_rust_unsafe_py(iterations: i32) -> PyResult<i64> {
+    // cleanupはDrop経由で呼ばれる（メモリリーク防止）
+    Ok(run_append_sum::<UnsafeDll>(iterations))
+}
+
+#[pyfunction]
+fn run_rust_bump_py(iterations: i32) -> PyResult<i64> {
+    Ok(try_run_append_sum::<BumpDll>(iterations)?)
+}
+
+#[pyfunction]
+fn run_wgpu_py(iterations: u32) -> PyResult<f64> {
+    // Native環境では非同期ランタイム(Tokio)を自分で用意して
+    // async関数を同期的にブロック実行する
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let result = rt.block_on(async {
+        run_wgpu_core(iterations).await
+    });
+    Ok(result)
+}
+
+/// GPU上のリストのチェックサム（{"len", "sum", "hash"}）
+#[pyfunction]
+fn run_wgpu_checksum_py(py: Python<'_>, iterations: u32) -> PyResult<PyObject> {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let checksum = rt
+        .block_on(run_wgpu_checksum(iterations))
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("WGPU readback failed"))?;
+    checksum_to_py(py, &checksum)
+}
+
+#[pyfunction]
+fn run_rust_bump_insert_py(iterations: i32) -> PyResult<()> {
+    // リストごとに専用のアリーナを確保する (BumpDll::with_capacity)
+    try_run_workload::<BumpDll>(&Workload::random_insert(iterations.max(0) as usize))?;
+    // ノード単位のcleanupは不要（アリーナごとDropで解放）
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_zipper_py(iterations: i32) -> PyResult<i64> {
+    // 実際に大量のメモリ確保が発生する (with_capacity)
+    Ok(run_append_sum::<ZipperList>(iterations))
+}
+
+#[pyfunction]
+fn run_rust_gap_buffer_py(iterations: i32) -> PyResult<i64> {
+    Ok(run_append_sum::<GapBuffer>(iterations))
+}
+
+// block_size < 2 は ValueError（Rust側のassertでabortさせない）
+fn unrolled_list(block_size: usize) -> PyResult<UnrolledList> {
+    if block_size < 2 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "block_size must be at least 2 (got {})",
+            block_size
+        )));
+    }
+    Ok(UnrolledList::with_block_size(block_size))
+}
+
+#[pyfunction]
+#[pyo3(signature = (iterations, block_size = DEFAULT_BLOCK_SIZE))]
+fn run_rust_unrolled_py(iterations: i32, block_size: usize) -> PyResult<i64> {
+    Ok(run_append_sum_on(unrolled_list(block_size)?, iterations))
+}
+
+#[pyfunction]
+fn run_rust_treap_py(iterations: i32) -> PyResult<i64> {
+    Ok(run_append_sum::<TreapList>(iterations))
+}
+
+#[pyfunction]
+fn run_rust_arena_dll_py(iterations: i32) -> PyResult<i64> {
+    Ok(run_append_sum::<ArenaDll>(iterations))
+}
+
+#[pyfunction]
+fn run_rust_soa_bump_py(iterations: i32) -> PyResult<i64> {
+    Ok(run_append_sum::<SoaBumpDll>(iterations))
+}
+
+#[pyfunction]
+#[pyo3(signature = (iterations, seed = DEFAULT_LEVEL_SEED))]
+fn run_rust_skiplist_py(iterations: i32, seed: u64) -> PyResult<i64> {
+    Ok(run_append_sum_on(SkipList::with_seed(seed), iterations))
+}
+
+
+#[pyfunction]
+fn run_rust_safe_insert_py(iterations: i32) -> PyResult<()> {
+    // 挿入テストは時間がかかるので回数を減らすことを推奨するが、引数に従う
+    // 乱数はRust側で簡易生成するか、Pythonからリストをもらうのが公平だが、
+    // ここでは簡易的に「毎回ランダム」ではなく「中央付近への挿入」などで負荷をかける
+    // 厳密なランダムアクセスはPython側で制御したほうが公平なため、
+    // ここでは「Pythonから位置と値のリストを受け取る」形がベストだが、
+    // 実装が複雑になるため、「疑似ランダム（線形合同法）」でRust内で完結させる。
+    run_random_insert::<DoublyLinkedList>(iterations);
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_unsafe_insert_py(iterations: i32) -> PyResult<()> {
+    run_random_insert::<UnsafeDll>(iterations);
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_zipper_insert_py(iterations: i32) -> PyResult<()> {
+    run_random_insert::<ZipperList>(iterations);
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_unsafe_zipper_insert_py(iterations: i32) -> PyResult<()> {
+    run_random_insert::<UnsafeZipperList>(iterations);
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_gap_buffer_insert_py(iterations: i32) -> PyResult<()> {
+    run_random_insert::<GapBuffer>(iterations);
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (iterations, block_size = DEFAULT_BLOCK_SIZE))]
+fn run_rust_unrolled_insert_py(iterations: i32, block_size: usize) -> PyResult<()> {
+    run_random_insert_on(unrolled_list(block_size)?, iterations);
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_treap_insert_py(iterations: i32) -> PyResult<()> {
+    // O(log N) なので N は数百万まで回せる
+    run_random_insert::<TreapList>(iterations);
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (iterations, seed = DEFAULT_LEVEL_SEED))]
+fn run_rust_skiplist_insert_py(iterations: i32, seed: u64) -> PyResult<()> {
+    // 同じ seed ならノードの高さの並びも同じになる
+    run_random_insert_on(SkipList::with_seed(seed), iterations);
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_arena_dll_insert_py(iterations: i32) -> PyResult<()> {
+    run_random_insert::<ArenaDll>(iterations);
+    Ok(())
+}
+
+#[pyfunction]
+fn run_rust_soa_bump_insert_py(iterations: i32) -> PyResult<()> {
+    run_random_insert::<SoaBumpDll>(iterations);
+    Ok(())
+}
+
+// ========================================================
+// WASM Runtime (Server-side WASM via Wasmtime)
+// ========================================================
+
+#[pyfunction]
+fn run_wasm_py(wasm_bytes: &[u8], func_name: &str, iterations: i32) -> PyResult<i32> {
+    use wasmtime::*;
+
+    // 1. エンジンの設定 (JITコンパイル有効)
+    let engine = Engine::default();
+
+    // 2. モジュールのコンパイル (バイナリ -> マシンコード)
+    // ※ベンチマークで「実行速度」だけを見たいなら、このコンパイル時間は計測から外すべきだが、
+    // 今回は「ロードして実行」のトータルを見ても面白い。
+    // (Python側でModuleキャッシュする手もあるが、まずは単純に毎回ロードする)
+    let module = Module::new(&engine, wasm_bytes)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    // 3. ストア(メモリ空間)の作成
+    let mut store = Store::new(&engine, ());
+
+    // 4. インスタンス化 (Importsが必要ならここで渡す)
+    // 今回のZig/WATはFreestandingでImports不要なので空でOK
+    let instance = Instance::new(&mut store, &module, &[])
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    // 5. 関数の取得 (型付きで取得して高速化)
+    let run_func = instance
+        .get_typed_func::<i32, i32>(&mut store, func_name)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Function '{}' not found: {}", func_name, e)))?;
+
+    // 6. 実行
+    let result = run_func
+        .call(&mut store, iterations)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    Ok(result)
+}
+
+// ========================================================
+// Workload Engine
+// ========================================================
+
+fn parse_backend(name: &str) -> PyResult<BackendKind> {
+    BackendKind::from_name(name).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("unknown backend '{}'", name))
+    })
+}
+
+/// 任意のバックエンドに対してWorkloadを実行し、結果のチェックサムを返す
+/// 例: run_workload_py("zipper", 30000, mix="editor", dist="sequential:8")
+#[pyfunction]
+#[pyo3(signature = (backend, ops, seed = 123456789, mix = "insert", dist = "uniform", prefill = 0))]
+fn run_workload_py(
+    backend: &str,
+    ops: usize,
+    seed: u64,
+    mix: &str,
+    dist: &str,
+    prefill: usize,
+) -> PyResult<i64> {
+    let kind = parse_backend(backend)?;
+    let workload = Workload::from_spec(ops, seed, mix, dist)
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+        .with_prefill(prefill);
+    Ok(with_backend!(kind, B => try_run_workload::<B>(&workload))?)
+}
+
+// ========================================================
+// Cross-Runtime Checksum
+// ========================================================
+
+fn checksum_to_py(py: Python<'_>, checksum: &Checksum) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    dict.set_item("len", checksum.len)?;
+    dict.set_item("sum", checksum.sum)?;
+    dict.set_item("hash", checksum.hash)?;
+    Ok(dict.into())
+}
+
+// checksum_to_py の逆（dict 以外でも "len" / "sum" / "hash" を添字で引ければよい）
+fn checksum_from_py(obj: &PyAny) -> PyResult<Checksum> {
+    Ok(Checksum {
+        len: obj.get_item("len")?.extract()?,
+        sum: obj.get_item("sum")?.extract()?,
+        hash: obj.get_item("hash")?.extract()?,
+    })
+}
+
+/// Workloadを流し込んだ後のリストの {"len", "sum", "hash"}（Zig/WAT/WGSL版と同じ値になる）
+/// 例: checksum_py("safe", 100000) は run_zig_dll(100000) 後の checksum_* と一致する
+#[pyfunction]
+#[pyo3(signature = (backend, ops, seed = 123456789, mix = "append", dist = "uniform", prefill = 0))]
+fn checksum_py(
+    py: Python<'_>,
+    backend: &str,
+    ops: usize,
+    seed: u64,
+    mix: &str,
+    dist: &str,
+    prefill: usize,
+) -> PyResult<PyObject> {
+    let kind = parse_backend(backend)?;
+    let workload = Workload::from_spec(ops, seed, mix, dist)
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+        .with_prefill(prefill);
+    let checksum = with_backend!(kind, B => try_workload_checksum::<B>(&workload))?;
+    checksum_to_py(py, &checksum)
+}
+
+// ========================================================
+// Parallel (rayon)
+// ========================================================
+
+// GILを手放して threads 本のプールで f を実行する（threads=0 は論理コア数）
+fn in_thread_pool<R, F>(py: Python<'_>, threads: usize, f: F) -> PyResult<R>
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    py.allow_threads(|| with_threads(threads, f))
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+}
+
+/// ZipperList を並列に一括構築して並列に足す（run_rust_zipper_py の並列版）
+#[pyfunction]
+#[pyo3(signature = (iterations, threads = 0))]
+fn run_rust_zipper_par_sum_py(py: Python<'_>, iterations: i32, threads: usize) -> PyResult<i64> {
+    in_thread_pool(py, threads, || run_par_zipper_sum(iterations))
+}
+
+#[pyfunction]
+#[pyo3(signature = (iterations, threads = 0))]
+fn run_rust_unsafe_zipper_par_sum_py(py: Python<'_>, iterations: i32, threads: usize) -> PyResult<i64> {
+    in_thread_pool(py, threads, || run_par_unsafe_zipper_sum(iterations))
+}
+
+/// 順番の入れ替わった SoaBumpDll をリストランキングで並べ直し、その {"len", "sum", "hash"} を返す
+#[pyfunction]
+#[pyo3(signature = (iterations, threads = 0))]
+fn run_rust_soa_bump_par_rank_py(py: Python<'_>, iterations: i32, threads: usize) -> PyResult<PyObject> {
+    let checksum = in_thread_pool(py, threads, || run_par_list_rank(iterations))?;
+    checksum_to_py(py, &checksum)
+}
+
+// ========================================================
+// Bulk Construction (buffer protocol)
+// ========================================================
+
+// バッファプロトコルのオブジェクトを i32 の Vec にする。
+// 要素が i32 のもの（array('i')・numpy の int32 など）はそのまま読み、
+// バイト列（bytes・bytearray・uint8 の配列）はネイティブエンディアンの i32 の並びとして読む
+fn buffer_to_i32(py: Python<'_>, data: &PyAny) -> PyResult<Vec<i32>> {
+    if let Ok(buffer) = PyBuffer::<i32>::get(data) {
+        return buffer.to_vec(py);
+    }
+    let bytes = PyBuffer::<u8>::get(data)
+        .map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                "expected a buffer of int32 or raw bytes, got '{}'",
+                data.get_type().name().unwrap_or("?")
+            ))
+        })?
+        .to_vec(py)?;
+    if bytes.len() % 4 != 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "byte buffer length {} is not a multiple of 4",
+            bytes.len()
+        )));
+    }
+    Ok(bytes.chunks_exact(4).map(|b| i32::from_ne_bytes([b[0], b[1], b[2], b[3]])).collect())
+}
+
+/// バッファの中身を `from_slice` で一括構築して合計を返す
+/// 例: from_buffer_sum_py("bump", array.array("i", range(1000)))
+#[pyfunction]
+fn from_buffer_sum_py(py: Python<'_>, backend: &str, data: &PyAny) -> PyResult<i64> {
+    let kind = parse_backend(backend)?;
+    let values = buffer_to_i32(py, data)?;
+    Ok(with_backend!(kind, B => try_from_slice_sum_i64::<B>(&values))?)
+}
+
+/// バッファの中身を一括構築したリストの {"len", "sum", "hash"}（append で作った場合と同じ値）
+#[pyfunction]
+fn from_buffer_checksum_py(py: Python<'_>, backend: &str, data: &PyAny) -> PyResult<PyObject> {
+    let kind = parse_backend(backend)?;
+    let values = buffer_to_i32(py, data)?;
+    let checksum = with_backend!(kind, B => try_from_slice_checksum::<B>(&values))?;
+    checksum_to_py(py, &checksum)
+}
+
+// ========================================================
+// Typed Lists (i32 / i64 / f64)
+// ========================================================
+
+/// values をバックエンドのリストに追加して合計を返す（要素型ごとに単相化したもの）
+/// 例: sum_f64_py("zipper", [0.5, 1.5])
+/// 整数の合計は溢れないよう広い型で取る（i32 は i64 で返し、i64 で収まらなければ OverflowError）
+#[pyfunction]
+fn sum_i32_py(backend: &str, values: Vec<i32>) -> PyResult<i64> {
+    let kind = parse_backend(backend)?;
+    Ok(with_backend!(kind, B<i32> => try_append_sum_i64::<i32, B>(&values))?)
+}
+
+#[pyfunction]
+fn sum_i64_py(backend: &str, values: Vec<i64>) -> PyResult<i64> {
+    let kind = parse_backend(backend)?;
+    let total = with_backend!(kind, B<i64> => try_append_sum_i128::<i64, B>(&values))?;
+    i64::try_from(total)
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyOverflowError, _>(format!("sum {} does not fit in i64", total)))
+}
+
+#[pyfunction]
+fn sum_f64_py(backend: &str, values: Vec<f64>) -> PyResult<f64> {
+    let kind = parse_backend(backend)?;
+    Ok(with_backend!(kind, B<f64> => try_append_sum::<f64, B>(&values))?)
+}
+
+// ========================================================
+// Operation Trace (Record & Replay)
+// ========================================================
+
+fn trace_err(e: TraceError) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+}
+
+/// トレース（バイナリ/テキストどちらでも可）を指定バックエンドで再生し、チェックサムを返す
+#[pyfunction]
+fn replay_trace_py(backend: &str, trace: &[u8]) -> PyResult<i64> {
+    let kind = parse_backend(backend)?;
+    let trace = Trace::parse(trace).map_err(trace_err)?;
+    Ok(trace.try_replay_on(kind)?)
+}
+
+#[pyfunction]
+fn trace_to_text_py(trace: &[u8]) -> PyResult<String> {
+    Ok(Trace::parse(trace).map_err(trace_err)?.to_text())
+}
+
+#[pyfunction]
+fn trace_to_binary_py(py: Python<'_>, trace: &[u8]) -> PyResult<PyObject> {
+    let trace = Trace::parse(trace).map_err(trace_err)?;
+    Ok(PyBytes::new(py, &trace.to_binary()).into())
+}
+
+/// 合成ワークロードをバイナリトレースとして書き出す
+#[pyfunction]
+#[pyo3(signature = (ops, seed = 123456789, mix = "insert", dist = "uniform", prefill = 0))]
+fn workload_trace_py(
+    py: Python<'_>,
+    ops: usize,
+    seed: u64,
+    mix: &str,
+    dist: &str,
+    prefill: usize,
+) -> PyResult<PyObject> {
+    let workload = Workload::from_spec(ops, seed, mix, dist)
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+        .with_prefill(prefill);
+    Ok(PyBytes::new(py, &Trace::from_workload(&workload).to_binary()).into())
+}
+
+/// Pythonアプリ側の操作を実リストに適用しつつ記録する
+/// 例: rec = TraceRecorder("zipper"); rec.insert(0, 5); data = rec.to_bytes()
+#[pyclass(unsendable)]
+struct TraceRecorder {
+    list: Box<dyn DynSequence>,
+    trace: Trace,
+}
+
+impl TraceRecorder {
+    // 確保に失敗した操作は記録しない
+    fn apply(&mut self, op: Op) -> PyResult<Option<i64>> {
+        let result = op.try_apply(&mut *self.list)?;
+        self.trace.push(op);
+        Ok(result)
+    }
+}
+
+#[pymethods]
+impl TraceRecorder {
+    #[new]
+    #[pyo3(signature = (backend = "zipper"))]
+    fn new(backend: &str) -> PyResult<Self> {
+        Ok(Self {
+            list: parse_backend(backend)?.boxed(),
+            trace: Trace::new(),
+        })
+    }
+
+    fn append(&mut self, value: i32) -> PyResult<()> {
+        self.apply(Op::Append(value)).map(|_| ())
+    }
+
+    fn insert(&mut self, index: usize, value: i32) -> PyResult<()> {
+        self.apply(Op::Insert(index, value)).map(|_| ())
+    }
+
+    fn remove(&mut self, index: usize) -> PyResult<Option<i64>> {
+        self.apply(Op::Remove(index))
+    }
+
+    fn get(&mut self, index: usize) -> PyResult<Option<i64>> {
+        self.apply(Op::Get(index))
+    }
+
+    fn sum(&mut self) -> PyResult<Option<i64>> {
+        self.apply(Op::Sum)
+    }
+
+    fn clear(&mut self) -> PyResult<()> {
+        self.apply(Op::Clear).map(|_| ())
+    }
+
+    fn __len__(&self) -> usize {
+        self.list.len()
+    }
+
+    /// 記録した操作数
+    #[getter]
+    fn ops(&self) -> usize {
+        self.trace.len()
+    }
+
+    fn to_bytes(&self, py: Python<'_>) -> PyObject {
+        PyBytes::new(py, &self.trace.to_binary()).into()
+    }
+
+    fn to_text(&self) -> String {
+        self.trace.to_text()
+    }
+}
+
+// ========================================================
+// Zipper Cursor
+// ========================================================
+
+/// カーソルを保持したまま編集できるリスト（エディタのバッファ向け）
+/// 例: buf = ZipperCursor([1, 2, 3]); buf.seek(1); buf.insert(9); buf.delete_after()
+#[pyclass(unsendable)]
+struct ZipperCursor {
+    list: ZipperList<i32>,
+}
+
+#[pymethods]
+impl ZipperCursor {
+    #[new]
+    #[pyo3(signature = (values = None))]
+    fn new(py: Python<'_>, values: Option<&PyAny>) -> PyResult<Self> {
+        let values = match values {
+            Some(values) => iterable_to_i32(py, values)?,
+            None => Vec::new(),
+        };
+        Ok(Self { list: <ZipperList as crate::SequenceBackend>::from_slice(&values) })
+    }
+
+    /// カーソルの位置（左にある要素数）
+    #[getter]
+    fn position(&self) -> usize {
+        self.list.position()
+    }
+
+    fn seek(&mut self, index: usize) {
+        self.list.seek(index);
+    }
+
+    fn move_left(&mut self) -> bool {
+        self.list.move_left()
+    }
+
+    fn move_right(&mut self) -> bool {
+        self.list.move_right()
+    }
+
+    fn peek_before(&self) -> Option<i32> {
+        self.list.peek_before().copied()
+    }
+
+    fn peek_after(&self) -> Option<i32> {
+        self.list.peek_after().copied()
+    }
+
+    fn replace_before(&mut self, value: i32) -> Option<i32> {
+        self.list.replace_before(value)
+    }
+
+    fn replace_after(&mut self, value: i32) -> Option<i32> {
+        self.list.replace_after(value)
+    }
+
+    /// カーソル位置に挿入して、カーソルを挿入した要素の右へ進める
+    fn insert(&mut self, value: i32) {
+        self.list.insert_before(value);
+    }
+
+    fn insert_after(&mut self, value: i32) {
+        self.list.insert_after(value);
+    }
+
+    fn delete_before(&mut self) -> Option<i32> {
+        self.list.delete_before()
+    }
+
+    fn delete_after(&mut self) -> Option<i32> {
+        self.list.delete_after()
+    }
+
+    fn sum(&self) -> i64 {
+        self.list.sum_i64()
+    }
+
+    fn to_list(&self) -> Vec<i32> {
+        self.list.to_vec()
+    }
+
+    fn __len__(&self) -> usize {
+        self.list.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ZipperCursor(len={}, position={})", self.list.len(), self.list.position())
+    }
+}
+
+// ========================================================
+// Sequence Classes
+// 各バックエンドを Python のシーケンスとして持てるクラス（len・添字・スライス・反復・比較）
+// ========================================================
+
+// Python の添字（負なら後ろから数える）を 0..len に直す
+fn sequence_index(index: isize, len: usize, what: &str) -> PyResult<usize> {
+    let resolved = if index < 0 { index + len as isize } else { index };
+    if resolved < 0 || resolved as usize >= len {
+        return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(format!("{} index out of range", what)));
+    }
+    Ok(resolved as usize)
+}
+
+// __getitem__ / __delitem__ の int の添字。isize に収まらない int も list と同じく IndexError にする
+// （pop と insert の引数は list でも OverflowError なのでそのまま）
+fn item_index(index: &PyAny, len: usize, what: &str) -> PyResult<usize> {
+    match index.extract::<isize>() {
+        Ok(index) => sequence_index(index, len, what),
+        Err(e) if e.is_instance_of::<pyo3::exceptions::PyOverflowError>(index.py()) => {
+            let message = "cannot fit 'int' into an index-sized integer";
+            Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(message))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// list.insert と同じく、範囲外の位置は端に丸める
+fn insert_position(index: isize, len: usize) -> usize {
+    if index < 0 {
+        len.saturating_sub(index.unsigned_abs())
+    } else {
+        (index as usize).min(len)
+    }
+}
+
+// コンストラクタ・extend の引数を i32 の Vec にする
+// （array('i') などの int32 のバッファはそのまま読み、それ以外は要素を1つずつ取り出す）
+fn iterable_to_i32(py: Python<'_>, values: &PyAny) -> PyResult<Vec<i32>> {
+    if let Ok(buffer) = PyBuffer::<i32>::get(values) {
+        return buffer.to_vec(py);
+    }
+    values.iter()?.map(|value| value?.extract::<i32>()).collect()
+}
+
+// スライスが指す要素を先頭から順に取り出す（step が正なら先頭から流し読みするだけ）
+fn slice_values<B: crate::SequenceBackend>(list: &B, slice: &PySlice) -> PyResult<Vec<i32>> {
+    let indices = slice.indices(list.len() as std::os::raw::c_long)?;
+    let count = indices.slicelength as usize;
+    if indices.step > 0 {
+        return Ok(list.iter().skip(indices.start as usize).step_by(indices.step as usize).take(count).collect());
+    }
+    let values = list.to_vec();
+    Ok((0..count).map(|k| values[(indices.start + k as isize * indices.step) as usize]).collect())
+}
+
+fn delete_items<B: crate::SequenceBackend>(list: &mut B, index: &PyAny, what: &str) -> PyResult<()> {
+    if let Ok(slice) = index.downcast::<PySlice>() {
+        let indices = slice.indices(list.len() as std::os::raw::c_long)?;
+        let count = indices.slicelength as usize;
+        let mut targets: Vec<usize> =
+            (0..count).map(|k| (indices.start + k as isize * indices.step) as usize).collect();
+        // 後ろから消せば、まだ消していない位置はずれない
+        targets.sort_unstable_by(|a, b| b.cmp(a));
+        for target in targets {
+            list.remove(target);
+        }
+        return Ok(());
+    }
+    let index = item_index(index, list.len(), what)?;
+    list.remove(index);
+    Ok(())
+}
+
+/// シーケンスクラスの `iter()` が返すイテレータ（作った時点の値のコピーを返す）
+#[pyclass(unsendable)]
+struct SequenceIter {
+    values: std::vec::IntoIter<i32>,
+}
+
+#[pymethods]
+impl SequenceIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<i32> {
+        self.values.next()
+    }
+}
+
+// `$class` を Python 名 `$name` のシーケンスクラスとして定義する（中身は `$list`）。
+// 比較の相手は list と、ここで定義した全クラス（要素ごとに比べる）
+macro_rules! sequence_classes {
+    ($($(#[doc = $doc:literal])* $class:ident($name:literal, $list:ty, $kind:ident);)*) => {
+        $(
+            $(#[doc = $doc])*
+            #[pyclass(unsendable, sequence, name = $name)]
+            struct $class {
+                list: $list,
+            }
+
+            #[pymethods]
+            impl $class {
+                #[new]
+                #[pyo3(signature = (values = None))]
+                fn new(py: Python<'_>, values: Option<&PyAny>) -> PyResult<Self> {
+                    let values = match values {
+                        Some(values) => iterable_to_i32(py, values)?,
+                        None => Vec::new(),
+                    };
+                    Ok(Self { list: <$list as crate::SequenceBackend>::try_from_slice(&values)? })
+                }
+
+                /// 中身のバックエンド名（`run_workload_py` などに渡す名前）
+                #[getter]
+                fn backend(&self) -> &'static str {
+                    BackendKind::$kind.name()
+                }
+
+                fn __len__(&self) -> usize {
+                    self.list.len()
+                }
+
+                fn __getitem__(&self, py: Python<'_>, index: &PyAny) -> PyResult<PyObject> {
+                    if let Ok(slice) = index.downcast::<PySlice>() {
+                        // list と同じく、スライスは同じ型の新しいリストになる
+                        let values = slice_values(&self.list, slice)?;
+                        let list = <$list as crate::SequenceBackend>::try_from_slice(&values)?;
+                        return Ok(Py::new(py, Self { list })?.into_py(py));
+                    }
+                    let index = item_index(index, self.list.len(), $name)?;
+                    Ok(self.list.get(index).into_py(py))
+                }
+
+                fn __delitem__(&mut self, index: &PyAny) -> PyResult<()> {
+                    delete_items(&mut self.list, index, $name)
+                }
+
+                fn __contains__(&self, value: &PyAny) -> bool {
+                    // i32 にならない値（範囲外の int や別の型）は入っていない
+                    match value.extract::<i32>() {
+                        Ok(value) => <$list as crate::SequenceBackend>::iter(&self.list).any(|item| item == value),
+                        Err(_) => false,
+                    }
+                }
+
+                /// 呼んだ時点の値を先頭から返す（反復中に変更しても影響しない）
+                fn __iter__(&self) -> SequenceIter {
+                    SequenceIter { values: self.list.to_vec().into_iter() }
+                }
+
+                fn __reversed__(&self) -> SequenceIter {
+                    let mut values = self.list.to_vec();
+                    values.reverse();
+                    SequenceIter { values: values.into_iter() }
+                }
+
+                fn __richcmp__(&self, py: Python<'_>, other: &PyAny, op: CompareOp) -> PyObject {
+                    let Some(other) = comparable_values(other) else {
+                        return py.NotImplemented();
+                    };
+                    let equal = other.len() == self.list.len()
+                        && <$list as crate::SequenceBackend>::iter(&self.list).eq(other.iter().copied());
+                    match op {
+                        CompareOp::Eq => equal.into_py(py),
+                        CompareOp::Ne => (!equal).into_py(py),
+                        _ => py.NotImplemented(),
+                    }
+                }
+
+                fn append(&mut self, value: i32) -> PyResult<()> {
+                    Ok(self.list.try_append(value)?)
+                }
+
+                /// 末尾に一括で追加する（`extend_from_slice` を使う）
+                fn extend(&mut self, py: Python<'_>, values: &PyAny) -> PyResult<()> {
+                    let values = iterable_to_i32(py, values)?;
+                    Ok(self.list.try_extend_from_slice(&values)?)
+                }
+
+                fn insert(&mut self, index: isize, value: i32) -> PyResult<()> {
+                    let index = insert_position(index, self.list.len());
+                    Ok(self.list.try_insert(index, value)?)
+                }
+
+                #[pyo3(signature = (index = -1))]
+                fn pop(&mut self, index: isize) -> PyResult<i32> {
+                    if self.list.is_empty() {
+                        return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(format!("pop from empty {}", $name)));
+                    }
+                    let index = sequence_index(index, self.list.len(), "pop")?;
+                    Ok(self.list.remove(index).expect("index checked above"))
+                }
+
+                /// 最初に見つかった位置（無ければ ValueError）
+                fn index(&self, value: i32) -> PyResult<usize> {
+                    <$list as crate::SequenceBackend>::iter(&self.list)
+                        .position(|item| item == value)
+                        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{} is not in {}", value, $name)))
+                }
+
+                fn count(&self, value: i32) -> usize {
+                    <$list as crate::SequenceBackend>::iter(&self.list).filter(|&item| item == value).count()
+                }
+
+                fn clear(&mut self) {
+                    self.list.clear();
+                }
+
+                fn sum(&self) -> i64 {
+                    self.list.sum_i64()
+                }
+
+                fn to_list(&self) -> Vec<i32> {
+                    self.list.to_vec()
+                }
+
+                fn __repr__(&self) -> String {
+                    format!("{}({:?})", $name, self.list.to_vec())
+                }
+            }
+        )*
+
+        // list か、シーケンスクラスのどれかなら、その値
+        fn comparable_values(other: &PyAny) -> Option<Vec<i32>> {
+            if let Ok(list) = other.downcast::<PyList>() {
+                return list.extract().ok();
+            }
+            $(
+                if let Ok(cell) = other.downcast::<PyCell<$class>>() {
+                    return Some(cell.borrow().list.to_vec());
+                }
+            )*
+            None
+        }
+
+        // モジュールに追加し、collections.abc.Sequence としても登録する
+        fn add_sequence_classes(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+            $(
+                m.add_class::<$class>()?;
+                PySequence::register::<$class>(py)?;
+            )*
+            Ok(())
+        }
+    };
+}
+
+sequence_classes! {
+    /// Rc<RefCell> の双方向リスト（カーソル付き）
+    PyDoublyLinkedList("DoublyLinkedList", DoublyLinkedList<i32>, Safe);
+    /// 生ポインタの双方向リスト（カーソル付き）
+    PyUnsafeDll("UnsafeDll", UnsafeDll<i32>, Unsafe);
+    /// バンプアロケータ上の双方向リスト（チャンクを継ぎ足すので容量の上限はない）
+    PyBumpList("BumpList", GrowableBumpDll<'static, i32>, BumpGrowable);
+    /// value / next / prev を別々の配列に持つバンプリスト
+    PySoaBumpList("SoaBumpList", SoaBumpDll<i32>, SoaBump);
+    /// 2本のスタックの間をカーソルにした配列
+    PyZipperList("ZipperList", ZipperList<i32>, Zipper);
+    PyUnsafeZipperList("UnsafeZipperList", UnsafeZipperList<i32>, UnsafeZipper);
+    /// 1本のバッファとギャップ
+    PyGapBuffer("GapBuffer", GapBuffer<i32>, GapBuffer);
+    /// 固定長配列のブロックを繋いだリスト
+    PyUnrolledList("UnrolledList", UnrolledList<i32>, Unrolled);
+    /// 位置をキーにした平衡二分木（挿入・削除・添字が O(log N)）
+    PyTreapList("TreapList", TreapList<i32>, Treap);
+    /// 幅付きリンクのスキップリスト（挿入・削除・添字が期待 O(log N)）
+    PySkipList("SkipList", SkipList<i32>, SkipList);
+    /// Vec に並べたノードを添字で繋いだ双方向リスト
+    PyArenaDll("ArenaDll", ArenaDll<i32>, ArenaDll);
+}
+
+// ========================================================
+// Differential Checker
+// ========================================================
+
+fn divergence_report(divergences: Vec<Divergence>) -> Option<String> {
+    if divergences.is_empty() {
+        return None;
+    }
+    let reports: Vec<String> = divergences.iter().map(|d| d.to_string()).collect();
+    Some(reports.join("\n"))
+}
+
+/// Workloadを全バックエンドとVec<i32>で実行し、食い違いがあればレポート文字列を返す（一致ならNone）
+#[pyfunction]
+#[pyo3(signature = (ops, seed = 123456789, mix = "editor", dist = "uniform", prefill = 0))]
+fn diff_check_py(ops: usize, seed: u64, mix: &str, dist: &str, prefill: usize) -> PyResult<Option<String>> {
+    let workload = Workload::from_spec(ops, seed, mix, dist)
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+        .with_prefill(prefill);
+    Ok(divergence_report(check_ops(&workload.generate())))
+}
+
+/// 記録済みトレースで差分検査する
+#[pyfunction]
+fn diff_check_trace_py(trace: &[u8]) -> PyResult<Option<String>> {
+    let trace = Trace::parse(trace).map_err(trace_err)?;
+    Ok(divergence_report(check_ops(&trace.ops)))
+}
+
+// ========================================================
+// Benchmark Runner
+// ========================================================
+
+// レポートを format に応じて dict / JSON文字列 / CSV文字列 で返す
+fn report_to_py(py: Python<'_>, reports: &[BenchmarkReport], format: Option<&str>) -> PyResult<PyObject> {
+    match format {
+        None => {
+            let json = match reports {
+                [one] => one.to_json(),
+                many => report::to_json(many),
+            };
+            Ok(py.import("json")?.call_method1("loads", (json,))?.into())
+        }
+        Some("json") => Ok(match reports {
+            [one] => one.to_json(),
+            many => report::to_json(many),
+        }
+        .into_py(py)),
+        Some("csv") => Ok(report::to_csv(reports).into_py(py)),
+        Some(other) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "unknown format '{}' (expected 'json' or 'csv')",
+            other
+        ))),
+    }
+}
+
+/// ウォームアップ・適応サンプリング付きでワークロードを計測し、BenchmarkReport を返す（時間はms）
+/// format=None なら dict、"json" / "csv" ならその文字列
+/// 例: bench_py("zipper", 30000, mix="insert")["total"]["median"]
+#[pyfunction]
+#[pyo3(signature = (
+    backend, ops, seed = 123456789, mix = "insert", dist = "uniform", prefill = 0,
+    warmup = 3, min_samples = 10, max_samples = 200, max_seconds = 10.0, format = None
+))]
+#[allow(clippy::too_many_arguments)]
+fn bench_py(
+    py: Python<'_>,
+    backend: &str,
+    ops: usize,
+    seed: u64,
+    mix: &str,
+    dist: &str,
+    prefill: usize,
+    warmup: usize,
+    min_samples: usize,
+    max_samples: usize,
+    max_seconds: f64,
+    format: Option<&str>,
+) -> PyResult<PyObject> {
+    let kind = parse_backend(backend)?;
+    let workload = Workload::from_spec(ops, seed, mix, dist)
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+        .with_prefill(prefill);
+    let config = BenchConfig {
+        warmup,
+        min_samples: min_samples.max(1),
+        max_samples: max_samples.max(min_samples).max(1),
+        max_time_ms: max_seconds * 1000.0,
+        ..BenchConfig::default()
+    };
+    let result = bench(kind, &workload, &config)?;
+    let report = BenchmarkReport::from_result(&result, &workload, Runtime::Pyo3);
+    report_to_py(py, &[report], format)
+}
+
+/// Python側で計測した時間（ms のリスト）からレポートを作る。
+/// checksum は checksum_py と同じ {"len", "sum", "hash"} の dict（省略するとチェックサムなし）
+/// 例: report_from_samples_py("Zig (WASM)", "wasmtime", times, 100000, workload="append-sum",
+///                            checksum=checksum_py("safe", 100000))
+#[pyfunction]
+#[pyo3(signature = (implementation, runtime, samples_ms, n, workload = "append-sum", checksum = None, format = None))]
+#[allow(clippy::too_many_arguments)]
+fn report_from_samples_py(
+    py: Python<'_>,
+    implementation: &str,
+    runtime: &str,
+    samples_ms: Vec<f64>,
+    n: usize,
+    workload: &str,
+    checksum: Option<&PyAny>,
+    format: Option<&str>,
+) -> PyResult<PyObject> {
+    let runtime: Runtime = runtime.parse().map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+    let checksum = checksum.map(checksum_from_py).transpose()?;
+    let report = BenchmarkReport::from_samples(
+        implementation,
+        runtime,
+        n,
+        WorkloadInfo::named(workload),
+        &samples_ms,
+        checksum,
+    );
+    report_to_py(py, &[report], format)
+}
+
+/// レポート（JSONの配列または単体）をCSVに変換する
+#[pyfunction]
+fn reports_to_csv_py(json: &str) -> PyResult<String> {
+    let reports = report::from_json(json)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    Ok(report::to_csv(&reports))
+}
+
+/// ベースラインと候補のレポート（JSONの配列または単体）を比較する
+/// format=None なら {"passed", "max_regression_pct", "entries": [...]} の dict、
+/// "json" ならその文字列、"text" なら表形式の文字列
+/// 例: assert compare_reports_py(open("base.json").read(), open("cand.json").read(), 5.0)["passed"]
+#[pyfunction]
+#[pyo3(signature = (baseline, candidate, max_regression_pct = 5.0, format = None))]
+fn compare_reports_py(
+    py: Python<'_>,
+    baseline: &str,
+    candidate: &str,
+    max_regression_pct: f64,
+    format: Option<&str>,
+) -> PyResult<PyObject> {
+    let parse = |json: &str| {
+        report::from_json(json).map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    };
+    let result = compare(&parse(baseline)?, &parse(candidate)?, max_regression_pct);
+    match format {
+        None => Ok(py.import("json")?.call_method1("loads", (result.to_json(),))?.into()),
+        Some("json") => Ok(result.to_json().into_py(py)),
+        Some("text") => Ok(result.to_string().into_py(py)),
+        Some(other) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "unknown format '{}' (expected 'json' or 'text')",
+            other
+        ))),
+    }
+}
+
+#[pymodule]
+fn polyglot_compute_lab(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(run_rust_dll_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_safe_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_unsafe_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_unsafe_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_bump_py, m)?)?; 
+    m.add_function(wrap_pyfunction!(run_rust_bump_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_zipper_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_zipper_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_unsafe_zipper_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_gap_buffer_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_gap_buffer_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_unrolled_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_unrolled_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_treap_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_treap_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_skiplist_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_skiplist_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_arena_dll_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_arena_dll_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_soa_bump_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_soa_bump_insert_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_zipper_par_sum_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_unsafe_zipper_par_sum_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rust_soa_bump_par_rank_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_wgpu_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_wasm_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_workload_py, m)?)?;
+    m.add_function(wrap_pyfunction!(checksum_py, m)?)?;
+    m.add_function(wrap_pyfunction!(run_wgpu_checksum_py, m)?)?;
+    m.add_function(wrap_pyfunction!(from_buffer_sum_py, m)?)?;
+    m.add_function(wrap_pyfunction!(from_buffer_checksum_py, m)?)?;
+    m.add_function(wrap_pyfunction!(sum_i32_py, m)?)?;
+    m.add_function(wrap_pyfunction!(sum_i64_py, m)?)?;
+    m.add_function(wrap_pyfunction!(sum_f64_py, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_trace_py, m)?)?;
+    m.add_function(wrap_pyfunction!(trace_to_text_py, m)?)?;
+    m.add_function(wrap_pyfunction!(trace_to_binary_py, m)?)?;
+    m.add_function(wrap_pyfunction!(workload_trace_py, m)?)?;
+    m.add_class::<TraceRecorder>()?;
+    m.add_class::<ZipperCursor>()?;
+    m.add_class::<SequenceIter>()?;
+    add_sequence_classes(py, m)?;
+    m.add_function(wrap_pyfunction!(diff_check_py, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_check_trace_py, m)?)?;
+    m.add_function(wrap_pyfunction!(bench_py, m)?)?;
+    m.add_function(wrap_pyfunction!(report_from_samples_py, m)?)?;
+    m.add_function(wrap_pyfunction!(reports_to_csv_py, m)?)?;
+    m.add_function(wrap_pyfunction!(compare_reports_py, m)?)?;
+    Ok(())
+}
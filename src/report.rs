@@ -0,0 +1,323 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bench::{BenchConfig, BenchResult, Measurement, Stats};
+use crate::checksum::Checksum;
+use crate::workload::Workload;
+
+// ========================================================
+// Benchmark Report
+// CIのダッシュボードで保存・比較できるよう、計測結果を JSON / CSV で書き出す
+// ========================================================
+
+/// 計測した実行環境
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    /// Rustから直接（CLIなど）
+    Native,
+    /// Python拡張 (maturin) 経由
+    Pyo3,
+    /// wasmtime で .wasm を実行
+    Wasmtime,
+    /// ブラウザ/Node の wasm-bindgen 経由
+    Browser,
+    /// WebGPU コンピュートシェーダ
+    Wgpu,
+}
+
+impl Runtime {
+    pub const ALL: &'static [Runtime] =
+        &[Runtime::Native, Runtime::Pyo3, Runtime::Wasmtime, Runtime::Browser, Runtime::Wgpu];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Runtime::Native => "native",
+            Runtime::Pyo3 => "pyo3",
+            Runtime::Wasmtime => "wasmtime",
+            Runtime::Browser => "browser",
+            Runtime::Wgpu => "wgpu",
+        }
+    }
+}
+
+impl fmt::Display for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Runtime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        Runtime::ALL
+            .iter()
+            .copied()
+            .find(|r| r.name() == s)
+            .ok_or_else(|| format!("unknown runtime '{}' (expected native, pyo3, wasmtime, browser or wgpu)", s))
+    }
+}
+
+/// 計測したワークロードの説明
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadInfo {
+    /// 比較時のキー（例: "insert/uniform", "append-sum"）
+    pub name: String,
+    pub mix: String,
+    pub dist: String,
+    pub seed: u64,
+    pub prefill: usize,
+}
+
+impl WorkloadInfo {
+    /// Workloadエンジン以外で計測したもの（Zig/WAT/WGPUのSumなど）用
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            mix: String::new(),
+            dist: String::new(),
+            seed: 0,
+            prefill: 0,
+        }
+    }
+}
+
+impl From<&Workload> for WorkloadInfo {
+    fn from(workload: &Workload) -> Self {
+        Self {
+            name: format!("{}/{}", workload.mix, workload.dist),
+            mix: workload.mix.to_string(),
+            dist: workload.dist.to_string(),
+            seed: workload.seed,
+            prefill: workload.prefill,
+        }
+    }
+}
+
+/// 計測したマシンの情報
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    pub pointer_width: u32,
+    pub crate_version: String,
+}
+
+impl HostInfo {
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            // wasm32 では取得できないので1とする
+            cpus: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            pointer_width: usize::BITS,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// 1実装 × 1ワークロード × 1ランタイム の計測結果
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub implementation: String,
+    pub runtime: Runtime,
+    /// ワークロードの操作数（prefill分は含まない）
+    pub n: usize,
+    pub workload: WorkloadInfo,
+    /// 構築＋操作。外部で計測した結果では None
+    pub build: Option<Stats>,
+    /// Drop/cleanup。外部で計測した結果では None
+    pub teardown: Option<Stats>,
+    pub total: Stats,
+    /// 操作を流し終えたリストのチェックサム。外部で計測して渡されなかった場合は None
+    pub checksum: Option<Checksum>,
+    pub host: HostInfo,
+    /// UNIX時刻（秒）。wasm32 では取得できないので None
+    pub timestamp: Option<u64>,
+}
+
+impl BenchmarkReport {
+    pub fn new(
+        implementation: &str,
+        runtime: Runtime,
+        workload: &Workload,
+        measurement: &Measurement,
+        checksum: Option<Checksum>,
+    ) -> Self {
+        Self {
+            implementation: implementation.to_string(),
+            runtime,
+            n: workload.ops,
+            workload: WorkloadInfo::from(workload),
+            build: Some(measurement.build),
+            teardown: Some(measurement.teardown),
+            total: measurement.total,
+            checksum,
+            host: HostInfo::current(),
+            timestamp: unix_time(),
+        }
+    }
+
+    pub fn from_result(result: &BenchResult, workload: &Workload, runtime: Runtime) -> Self {
+        Self::new(result.backend.name(), runtime, workload, &result.measurement, Some(result.checksum))
+    }
+
+    /// 呼び出し側で計測した所要時間（ms）からレポートを作る（Zig/WAT/WGPU/Pythonの計測用）
+    pub fn from_samples(
+        implementation: &str,
+        runtime: Runtime,
+        n: usize,
+        workload: WorkloadInfo,
+        samples_ms: &[f64],
+        checksum: Option<Checksum>,
+    ) -> Self {
+        Self {
+            implementation: implementation.to_string(),
+            runtime,
+            n,
+            workload,
+            build: None,
+            teardown: None,
+            total: Stats::from_samples(samples_ms, &BenchConfig::default()),
+            checksum,
+            host: HostInfo::current(),
+            timestamp: unix_time(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        // 全フィールドが素直な型なので失敗しない
+        serde_json::to_string_pretty(self).expect("report serialization")
+    }
+
+    pub fn csv_header() -> String {
+        let mut cols: Vec<String> = ["implementation", "runtime", "n", "workload", "mix", "dist", "seed", "prefill"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        cols.extend(CHECKSUM_FIELDS.iter().map(|field| format!("checksum_{}", field)));
+        for phase in ["build", "teardown", "total"] {
+            for field in STATS_FIELDS {
+                cols.push(format!("{}_{}", phase, field));
+            }
+        }
+        cols.extend(
+            ["os", "arch", "cpus", "pointer_width", "crate_version", "timestamp"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+        cols.join(",")
+    }
+
+    pub fn to_csv_row(&self) -> String {
+        let mut cols = vec![
+            csv_field(&self.implementation),
+            self.runtime.name().to_string(),
+            self.n.to_string(),
+            csv_field(&self.workload.name),
+            csv_field(&self.workload.mix),
+            csv_field(&self.workload.dist),
+            self.workload.seed.to_string(),
+            self.workload.prefill.to_string(),
+        ];
+        cols.extend(checksum_columns(self.checksum.as_ref()));
+        for stats in [self.build.as_ref(), self.teardown.as_ref(), Some(&self.total)] {
+            cols.extend(stats_columns(stats));
+        }
+        cols.push(csv_field(&self.host.os));
+        cols.push(csv_field(&self.host.arch));
+        cols.push(self.host.cpus.to_string());
+        cols.push(self.host.pointer_width.to_string());
+        cols.push(csv_field(&self.host.crate_version));
+        cols.push(self.timestamp.map(|t| t.to_string()).unwrap_or_default());
+        cols.join(",")
+    }
+}
+
+// CSVでの Checksum の列順
+const CHECKSUM_FIELDS: [&str; 3] = ["len", "sum", "hash"];
+
+fn checksum_columns(checksum: Option<&Checksum>) -> Vec<String> {
+    match checksum {
+        Some(c) => vec![c.len.to_string(), c.sum.to_string(), c.hash.to_string()],
+        None => vec![String::new(); CHECKSUM_FIELDS.len()],
+    }
+}
+
+// CSVでの Stats の列順
+const STATS_FIELDS: [&str; 10] =
+    ["samples", "outliers", "min", "max", "mean", "stddev", "median", "p95", "ci_low", "ci_high"];
+
+fn stats_columns(stats: Option<&Stats>) -> Vec<String> {
+    match stats {
+        Some(s) => vec![
+            s.samples.to_string(),
+            s.outliers.to_string(),
+            s.min.to_string(),
+            s.max.to_string(),
+            s.mean.to_string(),
+            s.stddev.to_string(),
+            s.median.to_string(),
+            s.p95.to_string(),
+            s.ci_low.to_string(),
+            s.ci_high.to_string(),
+        ],
+        None => vec![String::new(); STATS_FIELDS.len()],
+    }
+}
+
+// カンマ・引用符・改行を含む場合だけ引用する (RFC 4180)
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_time() -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+// wasm32-unknown-unknown では SystemTime::now() がパニックする
+#[cfg(target_arch = "wasm32")]
+fn unix_time() -> Option<u64> {
+    None
+}
+
+// --------------------------------------------------------
+// Report Set
+// --------------------------------------------------------
+
+/// レポートの配列を JSON で書き出す
+pub fn to_json(reports: &[BenchmarkReport]) -> String {
+    serde_json::to_string_pretty(reports).expect("report serialization")
+}
+
+/// JSON の配列、または単一のレポートを読む
+pub fn from_json(json: &str) -> Result<Vec<BenchmarkReport>, serde_json::Error> {
+    if json.trim_start().starts_with('{') {
+        Ok(vec![serde_json::from_str(json)?])
+    } else {
+        serde_json::from_str(json)
+    }
+}
+
+/// ヘッダー付きのCSVで書き出す
+pub fn to_csv(reports: &[BenchmarkReport]) -> String {
+    let mut out = BenchmarkReport::csv_header();
+    out.push('\n');
+    for report in reports {
+        out.push_str(&report.to_csv_row());
+        out.push('\n');
+    }
+    out
+}
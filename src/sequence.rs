@@ -0,0 +1,290 @@
+use std::iter::Sum;
+
+use crate::backends::AllocError;
+use crate::checksum::Checksum;
+
+// ========================================================
+// Common Interface
+// ========================================================
+
+/// 全てのリスト実装が共通で持つ操作。
+///
+/// ベンチマークのハーネスやPython/WASMのバインディングは、
+/// このトレイト越しにジェネリックに書く（実装ごとのコピペをしない）。
+///
+/// 要素型 `T` の既定は `i32`（Zig/WAT/WGSL版と同じ）。ワークロードやトレースは
+/// `i32` のリストに対して動く。`sum` は `T: Sum` のときだけ使える。
+pub trait SequenceBackend<T = i32> {
+    /// 先頭から順に値を返すイテレータ（実装ごとに具体型が異なる）
+    type Iter<'a>: Iterator<Item = T>
+    where
+        Self: 'a;
+
+    fn new() -> Self
+    where
+        Self: Sized;
+
+    /// 容量を事前確保できる実装（Vecベース）はここで確保する。
+    /// ノード単位で確保する実装では `new()` と同じ。
+    fn with_capacity(capacity: usize) -> Self
+    where
+        Self: Sized;
+
+    fn append(&mut self, value: T);
+
+    /// `index` の手前に挿入する。`index >= len()` の場合は末尾に追加する。
+    fn insert(&mut self, index: usize, value: T);
+
+    /// 容量に上限のある実装（固定容量の `BumpDll`）では確保失敗を返す。
+    /// それ以外の実装では `append` と同じで、失敗しない。
+    fn try_append(&mut self, value: T) -> Result<(), AllocError> {
+        self.append(value);
+        Ok(())
+    }
+
+    /// `insert` の確保失敗を返す版（`try_append` と同じ）
+    fn try_insert(&mut self, index: usize, value: T) -> Result<(), AllocError> {
+        self.insert(index, value);
+        Ok(())
+    }
+
+    /// `values` を末尾にまとめて追加する。
+    /// 既定は1つずつ `append` する。配列を持つ実装は一度にコピーし、
+    /// ノード単位の実装は確保と繋ぎ込みをまとめて行うよう上書きする。
+    fn extend_from_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        for value in values {
+            self.append(value.clone());
+        }
+    }
+
+    /// `extend_from_slice` の確保失敗を返す版。
+    /// 既定は1つずつ `try_append` し、失敗した時点までの要素は追加されたまま残る。
+    /// 確保に失敗しない実装は一括の `extend_from_slice` を呼ぶよう上書きする。
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError>
+    where
+        T: Clone,
+    {
+        for value in values {
+            self.try_append(value.clone())?;
+        }
+        Ok(())
+    }
+
+    /// `values` を先頭から順に持つリストを作る（`with_capacity` + `extend_from_slice`）
+    fn from_slice(values: &[T]) -> Self
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        let mut list = Self::with_capacity(values.len());
+        list.extend_from_slice(values);
+        list
+    }
+
+    /// `from_slice` の確保失敗を返す版
+    fn try_from_slice(values: &[T]) -> Result<Self, AllocError>
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        let mut list = Self::with_capacity(values.len());
+        list.try_extend_from_slice(values)?;
+        Ok(list)
+    }
+
+    /// `index` の要素を取り除いて返す。範囲外なら `None`。
+    fn remove(&mut self, index: usize) -> Option<T>;
+
+    /// 先頭の要素を取り除いて返す。
+    /// DLL系はheadを直接繋ぎ変えるのでO(1)で上書きする。
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    /// 末尾の要素を取り除いて返す。
+    fn pop_back(&mut self) -> Option<T> {
+        match self.len() {
+            0 => None,
+            len => self.remove(len - 1),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 先頭から順に足す。連続したメモリを持つ実装（Zipper）は上書きする。
+    fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        self.iter().sum()
+    }
+
+    /// i64で合計する（i32の要素なら2^32個までは溢れない）
+    fn sum_i64(&self) -> i64
+    where
+        T: Into<i64>,
+    {
+        self.iter().map(Into::into).sum()
+    }
+
+    /// i128で合計する（i64の要素用）
+    fn sum_i128(&self) -> i128
+    where
+        T: Into<i128>,
+    {
+        self.iter().map(Into::into).sum()
+    }
+
+    /// 要素数・合計・順序つきハッシュ（Zig/WAT/WGSL版と突き合わせる用）
+    fn checksum(&self) -> Checksum
+    where
+        T: Into<i32>,
+    {
+        Checksum::of(self.iter().map(Into::into))
+    }
+
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn clear(&mut self);
+}
+
+/// `SequenceBackend` のオブジェクト安全版。
+///
+/// 実装を実行時に選びたい場合（Pythonのクラスやトレースの記録など）に
+/// `Box<dyn DynSequence>` として使う。全ての `SequenceBackend` に自動で実装される。
+/// メソッド名が同じなので、両方のトレイトを同じスコープでimportしないこと。
+pub trait DynSequence<T = i32> {
+    fn append(&mut self, value: T);
+    fn insert(&mut self, index: usize, value: T);
+    fn try_append(&mut self, value: T) -> Result<(), AllocError> {
+        self.append(value);
+        Ok(())
+    }
+    fn try_insert(&mut self, index: usize, value: T) -> Result<(), AllocError> {
+        self.insert(index, value);
+        Ok(())
+    }
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError>
+    where
+        T: Clone,
+    {
+        for value in values {
+            self.try_append(value.clone())?;
+        }
+        Ok(())
+    }
+    fn remove(&mut self, index: usize) -> Option<T>;
+    fn pop_front(&mut self) -> Option<T>;
+    fn pop_back(&mut self) -> Option<T>;
+    fn get(&self, index: usize) -> Option<T>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool;
+    fn sum(&self) -> T
+    where
+        T: Sum;
+    fn sum_i64(&self) -> i64
+    where
+        T: Into<i64>;
+    fn sum_i128(&self) -> i128
+    where
+        T: Into<i128>;
+    fn checksum(&self) -> Checksum
+    where
+        T: Into<i32>;
+    fn clear(&mut self);
+    fn to_vec(&self) -> Vec<T>;
+}
+
+impl<T, B: SequenceBackend<T>> DynSequence<T> for B {
+    fn append(&mut self, value: T) {
+        SequenceBackend::append(self, value)
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        SequenceBackend::insert(self, index, value)
+    }
+
+    fn try_append(&mut self, value: T) -> Result<(), AllocError> {
+        SequenceBackend::try_append(self, value)
+    }
+
+    fn try_insert(&mut self, index: usize, value: T) -> Result<(), AllocError> {
+        SequenceBackend::try_insert(self, index, value)
+    }
+
+    fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), AllocError>
+    where
+        T: Clone,
+    {
+        SequenceBackend::try_extend_from_slice(self, values)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        SequenceBackend::remove(self, index)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        SequenceBackend::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        SequenceBackend::pop_back(self)
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        SequenceBackend::get(self, index)
+    }
+
+    fn len(&self) -> usize {
+        SequenceBackend::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        SequenceBackend::is_empty(self)
+    }
+
+    fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        SequenceBackend::sum(self)
+    }
+
+    fn sum_i64(&self) -> i64
+    where
+        T: Into<i64>,
+    {
+        SequenceBackend::sum_i64(self)
+    }
+
+    fn sum_i128(&self) -> i128
+    where
+        T: Into<i128>,
+    {
+        SequenceBackend::sum_i128(self)
+    }
+
+    fn checksum(&self) -> Checksum
+    where
+        T: Into<i32>,
+    {
+        SequenceBackend::checksum(self)
+    }
+
+    fn clear(&mut self) {
+        SequenceBackend::clear(self)
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        SequenceBackend::iter(self).collect()
+    }
+}
@@ -9,10 +9,18 @@ struct Allocator {
     counter: atomic<u32>,
 }
 
+// WGSLにはi64が無いので、合計は下位/上位の32bitに分けて持つ
 struct Result {
-    sum: i32,
+    len: u32,
+    sum_lo: u32,
+    sum_hi: u32,
+    hash: u32,
 }
 
+// Rust版の Checksum と同じ定数（h = h * PRIME + value mod 2^32）
+const HASH_SEED: u32 = 0x811c9dc5u;
+const HASH_PRIME: u32 = 0x01000193u;
+
 @group(0) @binding(0) var<storage, read_write> heap: array<Node>;
 @group(0) @binding(1) var<storage, read_write> alloc: Allocator;
 @group(0) @binding(2) var<storage, read_write> head_tail: array<u32, 2>;
@@ -50,11 +58,24 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 
     var current = head_tail[0];
-    var s = 0;
+    var len = 0u;
+    var sum_lo = 0u;
+    var sum_hi = 0u;
+    var hash = HASH_SEED;
     for (var k = 0u; k < iters + 10u; k++) {
         if (current == 0u) { break; }
-        s += heap[current].value;
+        let v = heap[current].value;
+        let bits = bitcast<u32>(v);
+        // 64bit加算: 下位の桁上がりと、負の値の符号拡張(0xffffffff)を上位に足す
+        let lo = sum_lo + bits;
+        sum_hi += select(0u, 1u, lo < sum_lo) + select(0u, 0xffffffffu, v < 0);
+        sum_lo = lo;
+        hash = hash * HASH_PRIME + bits;
+        len += 1u;
         current = heap[current].next;
     }
-    result.sum = s;
+    result.len = len;
+    result.sum_lo = sum_lo;
+    result.sum_hi = sum_hi;
+    result.hash = hash;
 }
\ No newline at end of file
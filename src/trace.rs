@@ -0,0 +1,568 @@
+use std::cell::{Ref, RefCell};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::checksum::Checksum;
+use crate::workload::{run_ops, try_run_ops, Op, Workload};
+use crate::{with_backend, AllocError, BackendKind, SequenceBackend};
+
+// ========================================================
+// Operation Trace
+// 実アプリの操作列を記録し、全バックエンドでそのまま再生する
+// ========================================================
+//
+// Binary format (little endian / LEB128):
+//   header : b"PCLT" + version(u8 = 1)
+//   record : opcode(u8) + operands
+//     0 Append  value
+//     1 Insert  index value
+//     2 Remove  index
+//     3 Get     index
+//     4 Sum
+//     5 Clear
+//   index は符号なしvarint, value はzigzag varint
+//
+// Text format (1行1操作, '#' 以降はコメント):
+//   append 5
+//   insert 3 7
+//   remove 2
+//   get 0
+//   sum
+//   clear
+
+const MAGIC: &[u8; 4] = b"PCLT";
+const VERSION: u8 = 1;
+
+const OP_APPEND: u8 = 0;
+const OP_INSERT: u8 = 1;
+const OP_REMOVE: u8 = 2;
+const OP_GET: u8 = 3;
+const OP_SUM: u8 = 4;
+const OP_CLEAR: u8 = 5;
+
+#[derive(Debug)]
+pub enum TraceError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    UnknownOpcode(u8),
+    /// このプラットフォームの usize に収まらない位置（wasm32 では u32 まで）
+    IndexOverflow(u64),
+    /// テキスト形式のパースエラー (行番号は1始まり)
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::Io(e) => write!(f, "trace I/O error: {}", e),
+            TraceError::BadMagic => write!(f, "not a binary trace (missing PCLT header)"),
+            TraceError::UnsupportedVersion(v) => write!(f, "unsupported trace version {}", v),
+            TraceError::Truncated => write!(f, "binary trace is truncated"),
+            TraceError::UnknownOpcode(op) => write!(f, "unknown opcode {}", op),
+            TraceError::IndexOverflow(index) => write!(f, "index {} does not fit in usize", index),
+            TraceError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl From<io::Error> for TraceError {
+    fn from(e: io::Error) -> Self {
+        TraceError::Io(e)
+    }
+}
+
+/// 記録された操作列
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub ops: Vec<Op>,
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, op: Op) {
+        self.ops.push(op);
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// 合成ワークロードをトレースとして保存したい場合に使う
+    pub fn from_workload(workload: &Workload) -> Self {
+        Self { ops: workload.generate() }
+    }
+
+    // --------------------------------------------------------
+    // Binary
+    // --------------------------------------------------------
+
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(5 + self.ops.len() * 4);
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        for op in &self.ops {
+            match *op {
+                Op::Append(value) => {
+                    out.push(OP_APPEND);
+                    write_varint(&mut out, zigzag(value));
+                }
+                Op::Insert(index, value) => {
+                    out.push(OP_INSERT);
+                    write_varint(&mut out, index as u64);
+                    write_varint(&mut out, zigzag(value));
+                }
+                Op::Remove(index) => {
+                    out.push(OP_REMOVE);
+                    write_varint(&mut out, index as u64);
+                }
+                Op::Get(index) => {
+                    out.push(OP_GET);
+                    write_varint(&mut out, index as u64);
+                }
+                Op::Sum => out.push(OP_SUM),
+                Op::Clear => out.push(OP_CLEAR),
+            }
+        }
+        out
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, TraceError> {
+        if bytes.len() < 5 || &bytes[..4] != MAGIC {
+            return Err(TraceError::BadMagic);
+        }
+        if bytes[4] != VERSION {
+            return Err(TraceError::UnsupportedVersion(bytes[4]));
+        }
+        let mut reader = ByteReader { bytes, pos: 5 };
+        let mut ops = Vec::new();
+        while let Some(opcode) = reader.next_byte() {
+            let op = match opcode {
+                OP_APPEND => Op::Append(unzigzag(reader.varint()?)),
+                OP_INSERT => {
+                    let index = reader.index()?;
+                    Op::Insert(index, unzigzag(reader.varint()?))
+                }
+                OP_REMOVE => Op::Remove(reader.index()?),
+                OP_GET => Op::Get(reader.index()?),
+                OP_SUM => Op::Sum,
+                OP_CLEAR => Op::Clear,
+                other => return Err(TraceError::UnknownOpcode(other)),
+            };
+            ops.push(op);
+        }
+        Ok(Self { ops })
+    }
+
+    // --------------------------------------------------------
+    // Text
+    // --------------------------------------------------------
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::from("# pcl-trace v1\n");
+        for op in &self.ops {
+            let line = match *op {
+                Op::Append(value) => format!("append {}", value),
+                Op::Insert(index, value) => format!("insert {} {}", index, value),
+                Op::Remove(index) => format!("remove {}", index),
+                Op::Get(index) => format!("get {}", index),
+                Op::Sum => "sum".to_string(),
+                Op::Clear => "clear".to_string(),
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, TraceError> {
+        let mut ops = Vec::new();
+        for (i, raw) in text.lines().enumerate() {
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |message: String| TraceError::Parse { line: i + 1, message };
+            let mut fields = line.split_whitespace();
+            let opcode = fields.next().unwrap_or("");
+            let args: Vec<&str> = fields.collect();
+            let arity = match opcode {
+                "append" | "remove" | "get" => 1,
+                "insert" => 2,
+                "sum" | "clear" => 0,
+                other => return Err(err(format!("unknown opcode '{}'", other))),
+            };
+            if args.len() != arity {
+                return Err(err(format!("'{}' takes {} operand(s), got {}", opcode, arity, args.len())));
+            }
+            let index = |s: &str| s.parse::<usize>().map_err(|_| err(format!("invalid index '{}'", s)));
+            let value = |s: &str| s.parse::<i32>().map_err(|_| err(format!("invalid value '{}'", s)));
+            let op = match opcode {
+                "append" => Op::Append(value(args[0])?),
+                "insert" => Op::Insert(index(args[0])?, value(args[1])?),
+                "remove" => Op::Remove(index(args[0])?),
+                "get" => Op::Get(index(args[0])?),
+                "sum" => Op::Sum,
+                _ => Op::Clear,
+            };
+            ops.push(op);
+        }
+        Ok(Self { ops })
+    }
+
+    /// 先頭のマジックでバイナリ/テキストを判別して読む
+    pub fn parse(bytes: &[u8]) -> Result<Self, TraceError> {
+        if bytes.starts_with(MAGIC) {
+            Self::from_binary(bytes)
+        } else {
+            let text = std::str::from_utf8(bytes).map_err(|_| TraceError::BadMagic)?;
+            Self::from_text(text)
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TraceError> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// 拡張子が `.txt` ならテキスト形式、それ以外はバイナリ形式で保存する
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), TraceError> {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            fs::write(path, self.to_text())?;
+        } else {
+            fs::write(path, self.to_binary())?;
+        }
+        Ok(())
+    }
+
+    // --------------------------------------------------------
+    // Replay
+    // --------------------------------------------------------
+
+    /// list にトレースを流し込み、結果のチェックサムを返す
+    pub fn replay<B: SequenceBackend>(&self, list: &mut B) -> i64 {
+        run_ops(list, self.ops.iter().copied())
+    }
+
+    /// 名前で指定したバックエンドの新しいリストに再生する
+    pub fn replay_on(&self, kind: BackendKind) -> i64 {
+        with_backend!(kind, B => {
+            let mut list = B::new();
+            self.replay(&mut list)
+        })
+    }
+
+    /// `replay_on` の確保失敗を返す版
+    pub fn try_replay_on(&self, kind: BackendKind) -> Result<i64, AllocError> {
+        with_backend!(kind, B => {
+            let mut list = B::new();
+            try_run_ops(&mut list, self.ops.iter().copied())
+        })
+    }
+}
+
+fn zigzag(value: i32) -> u64 {
+    ((value << 1) ^ (value >> 31)) as u32 as u64
+}
+
+fn unzigzag(raw: u64) -> i32 {
+    let raw = raw as u32;
+    ((raw >> 1) as i32) ^ -((raw & 1) as i32)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl ByteReader<'_> {
+    fn next_byte(&mut self) -> Option<u8> {
+        let b = *self.bytes.get(self.pos)?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn varint(&mut self) -> Result<u64, TraceError> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let b = self.next_byte().ok_or(TraceError::Truncated)?;
+            if shift >= 64 {
+                return Err(TraceError::Truncated);
+            }
+            value |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    // 位置の varint（as usize で切り詰めると壊れたトレースが別の位置で再生されてしまう）
+    fn index(&mut self) -> Result<usize, TraceError> {
+        let raw = self.varint()?;
+        usize::try_from(raw).map_err(|_| TraceError::IndexOverflow(raw))
+    }
+}
+
+// --------------------------------------------------------
+// Recorder
+// --------------------------------------------------------
+
+/// 任意のリストを包み、変更・読み取り操作 (get/sum) を全てトレースに記録する。
+/// `SequenceBackend` を実装しているので、既存コードのリスト型を差し替えるだけで使える。
+pub struct Recorder<B> {
+    inner: B,
+    // get/sum は &self なので内部可変性で記録する
+    trace: RefCell<Trace>,
+}
+
+impl<B: SequenceBackend> Recorder<B> {
+    pub fn wrap(inner: B) -> Self {
+        Self { inner, trace: RefCell::new(Trace::new()) }
+    }
+
+    pub fn trace(&self) -> Ref<'_, Trace> {
+        self.trace.borrow()
+    }
+
+    pub fn into_parts(self) -> (B, Trace) {
+        (self.inner, self.trace.into_inner())
+    }
+
+    fn record(&self, op: Op) {
+        self.trace.borrow_mut().push(op);
+    }
+}
+
+impl<B: SequenceBackend> SequenceBackend for Recorder<B> {
+    type Iter<'a> = B::Iter<'a> where B: 'a;
+
+    fn new() -> Self {
+        Self::wrap(B::new())
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::wrap(B::with_capacity(capacity))
+    }
+
+    fn append(&mut self, value: i32) {
+        self.record(Op::Append(value));
+        self.inner.append(value);
+    }
+
+    fn insert(&mut self, index: usize, value: i32) {
+        self.record(Op::Insert(index, value));
+        self.inner.insert(index, value);
+    }
+
+    // 確保に失敗した操作は記録しない（再生すると記録した実行と食い違う）
+    fn try_append(&mut self, value: i32) -> Result<(), AllocError> {
+        self.inner.try_append(value)?;
+        self.record(Op::Append(value));
+        Ok(())
+    }
+
+    fn try_insert(&mut self, index: usize, value: i32) -> Result<(), AllocError> {
+        self.inner.try_insert(index, value)?;
+        self.record(Op::Insert(index, value));
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<i32> {
+        self.record(Op::Remove(index));
+        self.inner.remove(index)
+    }
+
+    // pop系も位置指定のRemoveとして記録する（再生時は通常のremoveになる）
+    fn pop_front(&mut self) -> Option<i32> {
+        self.record(Op::Remove(0));
+        self.inner.pop_front()
+    }
+
+    fn pop_back(&mut self) -> Option<i32> {
+        let len = self.inner.len();
+        self.record(Op::Remove(len.saturating_sub(1)));
+        self.inner.pop_back()
+    }
+
+    fn get(&self, index: usize) -> Option<i32> {
+        self.record(Op::Get(index));
+        self.inner.get(index)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn sum(&self) -> i32 {
+        self.record(Op::Sum);
+        self.inner.sum()
+    }
+
+    fn sum_i64(&self) -> i64 {
+        self.record(Op::Sum);
+        self.inner.sum_i64()
+    }
+
+    fn sum_i128(&self) -> i128 {
+        self.record(Op::Sum);
+        self.inner.sum_i128()
+    }
+
+    // チェックサムも全要素を読むので Sum として記録する
+    fn checksum(&self) -> Checksum {
+        self.record(Op::Sum);
+        self.inner.checksum()
+    }
+
+    fn iter(&self) -> B::Iter<'_> {
+        self.inner.iter()
+    }
+
+    fn clear(&mut self) {
+        self.record(Op::Clear);
+        self.inner.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workload::{OpMix, PositionDist};
+    use crate::BumpDll;
+
+    fn sample() -> Trace {
+        let mut trace = Trace::from_workload(&Workload::new(500, 3, OpMix::EDITOR, PositionDist::Zipf(1.1)).with_prefill(20));
+        // 全ての操作と、varint・zigzag の境界の値
+        trace.ops.extend([
+            Op::Append(i32::MIN),
+            Op::Append(i32::MAX),
+            Op::Append(-1),
+            Op::Insert(0, 0),
+            Op::Insert(127, -64),
+            Op::Insert(128, 64),
+            Op::Remove(16_384),
+            Op::Get(u32::MAX as usize),
+            Op::Sum,
+            Op::Clear,
+        ]);
+        trace
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let trace = sample();
+        assert_eq!(Trace::parse(&trace.to_binary()).unwrap(), trace);
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let trace = sample();
+        assert_eq!(Trace::parse(trace.to_text().as_bytes()).unwrap(), trace);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let trace = sample();
+        let dir = std::env::temp_dir();
+        for name in ["pcl-trace-test.bin", "pcl-trace-test.txt"] {
+            let path = dir.join(format!("{}-{}", std::process::id(), name));
+            trace.save(&path).unwrap();
+            let loaded = Trace::load(&path);
+            let _ = fs::remove_file(&path);
+            assert_eq!(loaded.unwrap(), trace, "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        assert!(matches!(Trace::from_binary(b"PCLX\x01"), Err(TraceError::BadMagic)));
+        assert!(matches!(Trace::from_binary(b"PCL"), Err(TraceError::BadMagic)));
+        // マジックが無く UTF-8 でもない
+        assert!(matches!(Trace::parse(&[0xff, 0xfe, 0x00]), Err(TraceError::BadMagic)));
+    }
+
+    #[test]
+    fn rejects_bad_version() {
+        assert!(matches!(Trace::parse(b"PCLT\x02"), Err(TraceError::UnsupportedVersion(2))));
+    }
+
+    #[test]
+    fn rejects_truncated_varint() {
+        // 継続ビットが立ったまま終わる
+        assert!(matches!(Trace::parse(b"PCLT\x01\x01\x80"), Err(TraceError::Truncated)));
+        // Insert の値が無い
+        assert!(matches!(Trace::parse(b"PCLT\x01\x01\x05"), Err(TraceError::Truncated)));
+        // 64ビットに収まらない
+        let mut long = b"PCLT\x01\x02".to_vec();
+        long.extend([0x80; 10]);
+        long.push(0x01);
+        assert!(matches!(Trace::parse(&long), Err(TraceError::Truncated)));
+    }
+
+    #[test]
+    fn rejects_unknown_opcode_and_bad_text() {
+        assert!(matches!(Trace::parse(b"PCLT\x01\x09"), Err(TraceError::UnknownOpcode(9))));
+        assert!(matches!(Trace::parse(b"append 1\nfrob 2\n"), Err(TraceError::Parse { line: 2, .. })));
+        assert!(matches!(Trace::parse(b"insert 1\n"), Err(TraceError::Parse { line: 1, .. })));
+    }
+
+    #[test]
+    fn recorder_skips_failed_ops() {
+        let mut list = Recorder::wrap(BumpDll::<i32>::with_capacity(2));
+        list.try_append(1).unwrap();
+        list.try_insert(0, 2).unwrap();
+        assert!(list.try_append(3).is_err());
+        assert!(list.try_insert(0, 4).is_err());
+        let (inner, trace) = list.into_parts();
+        assert_eq!(trace.ops, vec![Op::Append(1), Op::Insert(0, 2)]);
+        // 再生すると記録したリストと同じになる
+        let mut replayed = crate::ZipperList::new();
+        trace.replay(&mut replayed);
+        assert!(replayed.iter().eq(inner.iter()));
+    }
+
+    #[test]
+    fn recorder_extend_stops_at_the_first_failed_append() {
+        // 一括版を持たない Recorder は1つずつ try_append する（以前は append で panic していた）
+        let mut list = Recorder::wrap(BumpDll::<i32>::with_capacity(2));
+        assert!(list.try_extend_from_slice(&[1, 2, 3, 4]).is_err());
+        let (inner, trace) = list.into_parts();
+        assert_eq!(trace.ops, vec![Op::Append(1), Op::Append(2)]);
+        assert!(inner.iter().eq([1, 2]));
+    }
+
+    #[test]
+    fn recorder_records_every_kind_of_sum() {
+        let mut list = Recorder::wrap(crate::ZipperList::new());
+        list.append(2);
+        list.append(3);
+        assert_eq!(list.sum(), 5);
+        assert_eq!(list.sum_i64(), 5);
+        assert_eq!(list.sum_i128(), 5);
+        assert_eq!(list.checksum(), Checksum::of([2, 3]));
+        let (_, trace) = list.into_parts();
+        assert_eq!(trace.ops, vec![Op::Append(2), Op::Append(3), Op::Sum, Op::Sum, Op::Sum, Op::Sum]);
+    }
+}
@@ -0,0 +1,296 @@
+// --------------------------------------------------------
+// WASM Interface
+// --------------------------------------------------------
+use wasm_bindgen::prelude::*;
+
+use js_sys::JSON;
+
+use crate::bench::{bench, BenchConfig};
+use crate::gpu::{run_wgpu_checksum, run_wgpu_core};
+use crate::checksum::Checksum;
+use crate::harness::{
+    run_append_sum, run_append_sum_on, run_random_insert, run_random_insert_on, try_append_sum, try_append_sum_i128,
+    try_append_sum_i64, try_from_slice_checksum, try_from_slice_sum_i64, try_run_append_sum, try_run_workload,
+    try_workload_checksum,
+};
+use crate::report::{self, BenchmarkReport, Runtime, WorkloadInfo};
+use crate::trace::Trace;
+use crate::workload::Workload;
+use crate::{
+    with_backend, AllocError, ArenaDll, BackendKind, BumpDll, DoublyLinkedList, GapBuffer, SkipList, SoaBumpDll,
+    TreapList, UnrolledList, UnsafeDll,
+};
+
+// アリーナの容量不足はJSの例外にする（panicさせるとWASMインスタンスごとtrapする）
+impl From<AllocError> for JsValue {
+    fn from(e: AllocError) -> Self {
+        JsValue::from_str(&e.to_string())
+    }
+}
+
+#[wasm_bindgen]
+pub fn run_rust_dll(iterations: i32) -> i64 {
+    run_append_sum::<DoublyLinkedList>(iterations)
+}
+
+// WASM Export for Unsafe Rust
+#[wasm_bindgen]
+pub fn run_rust_unsafe(iterations: i32) -> i64 {
+    // 計測後に掃除 (ベンチマーク外で呼ぶのが理想だが、WASMのメモリ圧迫を防ぐためここで呼ぶ)
+    // ※厳密な生成+トラバーサル速度比較のため、cleanupの時間はノイズになる可能性があるが、
+    //  Rust(Safe)はDropコストを支払っているため、ここでも支払うのが公平。
+    //  (cleanupはDrop経由で呼ばれる)
+    run_append_sum::<UnsafeDll>(iterations)
+}
+
+// WASM Export for Bump
+#[wasm_bindgen]
+pub fn run_rust_bump(iterations: i32) -> Result<i64, JsValue> {
+    Ok(try_run_append_sum::<BumpDll>(iterations)?)
+    // ノード単位の解放は不要（アリーナごとDropで解放）
+}
+
+// WASM Export for Gap Buffer
+#[wasm_bindgen]
+pub fn run_rust_gap_buffer(iterations: i32) -> i64 {
+    run_append_sum::<GapBuffer>(iterations)
+}
+
+#[wasm_bindgen]
+pub fn run_rust_gap_buffer_insert(iterations: i32) {
+    run_random_insert::<GapBuffer>(iterations);
+}
+
+// block_size < 2 はJSの例外にする（Rust側のassertでtrapさせない）
+fn unrolled_list(block_size: u32) -> Result<UnrolledList, JsValue> {
+    if block_size < 2 {
+        return Err(JsValue::from_str(&format!("block_size must be at least 2 (got {})", block_size)));
+    }
+    Ok(UnrolledList::with_block_size(block_size as usize))
+}
+
+// WASM Export for Unrolled Linked List
+#[wasm_bindgen]
+pub fn run_rust_unrolled(iterations: i32, block_size: u32) -> Result<i64, JsValue> {
+    Ok(run_append_sum_on(unrolled_list(block_size)?, iterations))
+}
+
+#[wasm_bindgen]
+pub fn run_rust_unrolled_insert(iterations: i32, block_size: u32) -> Result<(), JsValue> {
+    run_random_insert_on(unrolled_list(block_size)?, iterations);
+    Ok(())
+}
+
+// WASM Export for Implicit Treap
+#[wasm_bindgen]
+pub fn run_rust_treap(iterations: i32) -> i64 {
+    run_append_sum::<TreapList>(iterations)
+}
+
+#[wasm_bindgen]
+pub fn run_rust_treap_insert(iterations: i32) {
+    run_random_insert::<TreapList>(iterations);
+}
+
+// WASM Export for Indexable Skip List
+// seed はノードの高さを決める乱数の種（同じ seed なら同じ形になる）
+#[wasm_bindgen]
+pub fn run_rust_skiplist(iterations: i32, seed: u32) -> i64 {
+    run_append_sum_on(SkipList::with_seed(seed as u64), iterations)
+}
+
+#[wasm_bindgen]
+pub fn run_rust_skiplist_insert(iterations: i32, seed: u32) {
+    run_random_insert_on(SkipList::with_seed(seed as u64), iterations);
+}
+
+// WASM Export for Index Arena DLL
+#[wasm_bindgen]
+pub fn run_rust_arena_dll(iterations: i32) -> i64 {
+    run_append_sum::<ArenaDll>(iterations)
+}
+
+#[wasm_bindgen]
+pub fn run_rust_arena_dll_insert(iterations: i32) {
+    run_random_insert::<ArenaDll>(iterations);
+}
+
+// WASM Export for SoA Bump
+#[wasm_bindgen]
+pub fn run_rust_soa_bump(iterations: i32) -> i64 {
+    run_append_sum::<SoaBumpDll>(iterations)
+}
+
+#[wasm_bindgen]
+pub fn run_rust_soa_bump_insert(iterations: i32) {
+    run_random_insert::<SoaBumpDll>(iterations);
+}
+
+// WASM Export for Workload Engine
+// 例: run_rust_workload("zipper", 30000, 123456789, "editor", "sequential:8")
+#[wasm_bindgen]
+pub fn run_rust_workload(backend: &str, ops: u32, seed: u32, mix: &str, dist: &str) -> Result<i64, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    let workload = Workload::from_spec(ops as usize, seed as u64, mix, dist).map_err(|e| JsValue::from_str(&e))?;
+    Ok(with_backend!(kind, B => try_run_workload::<B>(&workload))?)
+}
+
+// WASM Export for Typed Lists (要素型ごとに単相化したもの)
+// 例: sum_f64("zipper", new Float64Array([0.5, 1.5])) / sum_i64("bump", new BigInt64Array([1n, 2n]))
+// 整数の合計は溢れないよう広い型で取る（i32 は i64 = BigInt で返し、i64 で収まらなければ例外）
+#[wasm_bindgen]
+pub fn sum_i32(backend: &str, values: &[i32]) -> Result<i64, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    Ok(with_backend!(kind, B<i32> => try_append_sum_i64::<i32, B>(values))?)
+}
+
+#[wasm_bindgen]
+pub fn sum_i64(backend: &str, values: &[i64]) -> Result<i64, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    let total = with_backend!(kind, B<i64> => try_append_sum_i128::<i64, B>(values))?;
+    i64::try_from(total).map_err(|_| JsValue::from_str(&format!("sum {} does not fit in i64", total)))
+}
+
+#[wasm_bindgen]
+pub fn sum_f64(backend: &str, values: &[f64]) -> Result<f64, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    Ok(with_backend!(kind, B<f64> => try_append_sum::<f64, B>(values))?)
+}
+
+// WASM Export for Cross-Runtime Checksum
+// 戻り値: { len, sum, hash }（Zig/WATの checksum_* エクスポートと同じ値になる）
+// 例: checksum_rust("safe", 100000, 0, "append", "uniform")
+#[wasm_bindgen]
+pub fn checksum_rust(backend: &str, ops: u32, seed: u32, mix: &str, dist: &str) -> Result<JsValue, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    let workload = Workload::from_spec(ops as usize, seed as u64, mix, dist).map_err(|e| JsValue::from_str(&e))?;
+    let checksum = with_backend!(kind, B => try_workload_checksum::<B>(&workload))?;
+    checksum_to_js(&checksum)
+}
+
+fn checksum_to_js(checksum: &Checksum) -> Result<JsValue, JsValue> {
+    let json = serde_json::to_string(checksum).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    JSON::parse(&json)
+}
+
+// checksum_to_js の逆（undefined / null は None）
+fn checksum_from_js(value: &JsValue) -> Result<Option<Checksum>, JsValue> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+    let json: String = JSON::stringify(value)?.into();
+    serde_json::from_str(&json).map(Some).map_err(|e| JsValue::from_str(&format!("checksum: {}", e)))
+}
+
+// WASM Export for Bulk Construction (Int32Array を from_slice で一括構築)
+// 例: from_int32_array_sum("bump", new Int32Array([1, 2, 3]))
+#[wasm_bindgen]
+pub fn from_int32_array_sum(backend: &str, values: &[i32]) -> Result<i64, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    Ok(with_backend!(kind, B => try_from_slice_sum_i64::<B>(values))?)
+}
+
+// 戻り値: { len, sum, hash }（同じ値を append で作った場合と一致する）
+#[wasm_bindgen]
+pub fn from_int32_array_checksum(backend: &str, values: &[i32]) -> Result<JsValue, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    let checksum = with_backend!(kind, B => try_from_slice_checksum::<B>(values))?;
+    checksum_to_js(&checksum)
+}
+
+// WASM Export for Trace Replay (バイナリ/テキストどちらでも可)
+#[wasm_bindgen]
+pub fn replay_trace(backend: &str, trace: &[u8]) -> Result<i64, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    let trace = Trace::parse(trace).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(trace.try_replay_on(kind)?)
+}
+
+// WASM Export for Benchmark Runner
+// 戻り値: BenchmarkReport のJSONをパースしたオブジェクト（時間はms）
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn bench_rust_workload(
+    backend: &str,
+    ops: u32,
+    seed: u32,
+    mix: &str,
+    dist: &str,
+    warmup: u32,
+    min_samples: u32,
+    max_samples: u32,
+    max_ms: f64,
+) -> Result<JsValue, JsValue> {
+    let kind = BackendKind::from_name(backend)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown backend '{}'", backend)))?;
+    let workload = Workload::from_spec(ops as usize, seed as u64, mix, dist).map_err(|e| JsValue::from_str(&e))?;
+    let config = BenchConfig {
+        warmup: warmup as usize,
+        min_samples: min_samples.max(1) as usize,
+        max_samples: max_samples.max(min_samples).max(1) as usize,
+        max_time_ms: max_ms,
+        ..BenchConfig::default()
+    };
+    let result = bench(kind, &workload, &config)?;
+    let report = BenchmarkReport::from_result(&result, &workload, Runtime::Browser);
+    JSON::parse(&report.to_json())
+}
+
+// JS側で計測した時間（Zig/WAT/WGPU）をレポートにする
+// checksum は checksum_rust と同じ { len, sum, hash }（undefined / null ならチェックサムなし）
+// 例: report_from_samples("Zig (WASM)", "browser", new Float64Array(times), 100000, "append-sum",
+//                         checksum_rust("safe", 100000, 0, "append", "uniform"))
+#[wasm_bindgen]
+pub fn report_from_samples(
+    implementation: &str,
+    runtime: &str,
+    samples_ms: &[f64],
+    n: u32,
+    workload: &str,
+    checksum: JsValue,
+) -> Result<JsValue, JsValue> {
+    let runtime: Runtime = runtime.parse().map_err(|e: String| JsValue::from_str(&e))?;
+    let checksum = checksum_from_js(&checksum)?;
+    let report = BenchmarkReport::from_samples(
+        implementation,
+        runtime,
+        n as usize,
+        WorkloadInfo::named(workload),
+        samples_ms,
+        checksum,
+    );
+    JSON::parse(&report.to_json())
+}
+
+// レポート（JSON.stringify した配列または単体）をCSVに変換する
+#[wasm_bindgen]
+pub fn reports_to_csv(json: &str) -> Result<String, JsValue> {
+    let reports = report::from_json(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(report::to_csv(&reports))
+}
+
+// --------------------------------------------------------
+// WASM Interface (Wrapper for WGPU)
+// --------------------------------------------------------
+#[wasm_bindgen]
+pub async fn run_wgpu_dll(iterations: u32) -> f64 {
+    // WASM環境ではJSのイベントループがよしなにやってくれるので、
+    // 単にasync関数を呼ぶだけでOK
+    run_wgpu_core(iterations).await
+}
+
+#[wasm_bindgen]
+pub async fn run_wgpu_dll_checksum(iterations: u32) -> Result<JsValue, JsValue> {
+    let checksum = run_wgpu_checksum(iterations)
+        .await
+        .ok_or_else(|| JsValue::from_str("WGPU readback failed"))?;
+    checksum_to_js(&checksum)
+}
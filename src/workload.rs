@@ -0,0 +1,578 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::sequence::DynSequence;
+use crate::{AllocError, SequenceBackend};
+
+// ========================================================
+// Workload Engine
+// シードから操作列を生成し、任意のバックエンドに流し込む
+// ========================================================
+
+/// リストに対する1操作
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Append(i32),
+    Insert(usize, i32),
+    Remove(usize),
+    Get(usize),
+    Sum,
+    /// 全要素の削除（Workloadは生成しない。トレースの記録用）
+    Clear,
+}
+
+impl Op {
+    /// 操作を実行し、結果（取り出した値/読んだ値/合計）を返す。
+    /// Append/Insert/Clear と範囲外の Remove/Get は `None`。
+    pub fn apply<B: DynSequence + ?Sized>(self, list: &mut B) -> Option<i64> {
+        match self {
+            Op::Append(value) => {
+                list.append(value);
+                None
+            }
+            Op::Insert(index, value) => {
+                list.insert(index, value);
+                None
+            }
+            Op::Remove(index) => list.remove(index).map(i64::from),
+            Op::Get(index) => list.get(index).map(i64::from),
+            // i32で合計すると溢れるので、i64で合計する
+            Op::Sum => Some(list.sum_i64()),
+            Op::Clear => {
+                list.clear();
+                None
+            }
+        }
+    }
+
+    /// `apply` の確保失敗を返す版（固定容量の `BumpDll` 用）
+    pub fn try_apply<B: DynSequence + ?Sized>(self, list: &mut B) -> Result<Option<i64>, AllocError> {
+        match self {
+            Op::Append(value) => list.try_append(value).map(|_| None),
+            Op::Insert(index, value) => list.try_insert(index, value).map(|_| None),
+            other => Ok(other.apply(list)),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// Operation Mix
+// --------------------------------------------------------
+
+/// 各操作の出現比率（重み）。合計が0の場合は全てAppendとみなす。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpMix {
+    pub append: u32,
+    pub insert: u32,
+    pub remove: u32,
+    pub get: u32,
+    pub sum: u32,
+}
+
+impl OpMix {
+    /// 末尾追加のみ (Sumベンチマークの構築部分)
+    pub const APPEND: OpMix = OpMix { append: 1, insert: 0, remove: 0, get: 0, sum: 0 };
+    /// 位置指定の挿入のみ (Random Insertベンチマーク)
+    pub const INSERT: OpMix = OpMix { append: 0, insert: 1, remove: 0, get: 0, sum: 0 };
+    /// エディタ風: 挿入が中心で、削除と参照が混ざる
+    pub const EDITOR: OpMix = OpMix { append: 0, insert: 6, remove: 3, get: 1, sum: 0 };
+    /// キュー風: 末尾に積んで位置指定で取り出す（Head分布と組み合わせるとFIFO）
+    pub const QUEUE: OpMix = OpMix { append: 1, insert: 0, remove: 1, get: 0, sum: 0 };
+
+    fn total(&self) -> u64 {
+        self.append as u64 + self.insert as u64 + self.remove as u64 + self.get as u64 + self.sum as u64
+    }
+
+    /// 重みが1種類だけなら乱数を消費せずに済む（旧LCGループと同じ位置列を再現するため）
+    fn single(&self) -> Option<OpKind> {
+        let kinds = [
+            (self.append, OpKind::Append),
+            (self.insert, OpKind::Insert),
+            (self.remove, OpKind::Remove),
+            (self.get, OpKind::Get),
+            (self.sum, OpKind::Sum),
+        ];
+        let mut nonzero = kinds.iter().filter(|(w, _)| *w > 0);
+        match (nonzero.next(), nonzero.next()) {
+            (Some(&(_, kind)), None) => Some(kind),
+            (None, _) => Some(OpKind::Append),
+            _ => None,
+        }
+    }
+
+    fn pick(&self, r: u64) -> OpKind {
+        let mut r = r % self.total();
+        for (w, kind) in [
+            (self.append, OpKind::Append),
+            (self.insert, OpKind::Insert),
+            (self.remove, OpKind::Remove),
+            (self.get, OpKind::Get),
+        ] {
+            if r < w as u64 {
+                return kind;
+            }
+            r -= w as u64;
+        }
+        OpKind::Sum
+    }
+}
+
+/// プリセット名 (`insert`, `append`, `editor`, `queue`) か
+/// `append=1,insert=3,remove=1` 形式の重み指定を受け付ける
+impl FromStr for OpMix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "append" => return Ok(OpMix::APPEND),
+            "insert" => return Ok(OpMix::INSERT),
+            "editor" => return Ok(OpMix::EDITOR),
+            "queue" => return Ok(OpMix::QUEUE),
+            _ => {}
+        }
+        let mut mix = OpMix { append: 0, insert: 0, remove: 0, get: 0, sum: 0 };
+        for part in s.split(',') {
+            let (key, weight) = part
+                .split_once('=')
+                .ok_or_else(|| format!("invalid op mix entry '{}' (expected name=weight)", part))?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid weight '{}' for '{}'", weight, key))?;
+            match key.trim() {
+                "append" => mix.append = weight,
+                "insert" => mix.insert = weight,
+                "remove" => mix.remove = weight,
+                "get" => mix.get = weight,
+                "sum" => mix.sum = weight,
+                other => return Err(format!("unknown op '{}' in op mix", other)),
+            }
+        }
+        Ok(mix)
+    }
+}
+
+// プリセットと一致する場合はその名前で表示する（レポートの workload 名に使う）
+impl fmt::Display for OpMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (preset, name) in [
+            (OpMix::APPEND, "append"),
+            (OpMix::INSERT, "insert"),
+            (OpMix::EDITOR, "editor"),
+            (OpMix::QUEUE, "queue"),
+        ] {
+            if *self == preset {
+                return f.write_str(name);
+            }
+        }
+        write!(
+            f,
+            "append={},insert={},remove={},get={},sum={}",
+            self.append, self.insert, self.remove, self.get, self.sum
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OpKind {
+    Append,
+    Insert,
+    Remove,
+    Get,
+    Sum,
+}
+
+// --------------------------------------------------------
+// Position Distribution
+// --------------------------------------------------------
+
+/// 位置指定の操作 (insert/remove/get) がどこを狙うか
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PositionDist {
+    /// 0..len の一様分布 (旧LCGループと同じ `seed % len`)
+    Uniform,
+    /// 先頭寄り: len * u^2
+    FrontBiased,
+    /// 末尾寄り: len - 1 - len * u^2
+    BackBiased,
+    /// 先頭からの順位に対するZipf分布 (指数 s)。連続近似の逆関数法でサンプリングする
+    Zipf(f64),
+    /// エディタのカーソル: 前回位置から ±max_step だけ動く。挿入後はカーソルが1つ進む
+    Sequential(usize),
+    /// 常に先頭
+    Head,
+    /// 常に末尾の要素
+    Tail,
+}
+
+/// `uniform`, `front`, `back`, `zipf[:s]`, `sequential[:step]`, `head`, `tail`
+impl FromStr for PositionDist {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (name, arg) = match s.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (s, None),
+        };
+        match name {
+            "uniform" => Ok(PositionDist::Uniform),
+            "front" => Ok(PositionDist::FrontBiased),
+            "back" => Ok(PositionDist::BackBiased),
+            "zipf" => {
+                let exponent = match arg {
+                    Some(a) => a.parse().map_err(|_| format!("invalid zipf exponent '{}'", a))?,
+                    None => 1.0,
+                };
+                Ok(PositionDist::Zipf(exponent))
+            }
+            "sequential" => {
+                let step = match arg {
+                    Some(a) => a.parse().map_err(|_| format!("invalid sequential step '{}'", a))?,
+                    None => 1,
+                };
+                Ok(PositionDist::Sequential(step))
+            }
+            "head" => Ok(PositionDist::Head),
+            "tail" => Ok(PositionDist::Tail),
+            other => Err(format!("unknown position distribution '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for PositionDist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionDist::Uniform => write!(f, "uniform"),
+            PositionDist::FrontBiased => write!(f, "front"),
+            PositionDist::BackBiased => write!(f, "back"),
+            PositionDist::Zipf(s) => write!(f, "zipf:{}", s),
+            PositionDist::Sequential(step) => write!(f, "sequential:{}", step),
+            PositionDist::Head => write!(f, "head"),
+            PositionDist::Tail => write!(f, "tail"),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// Workload
+// --------------------------------------------------------
+
+/// シードと操作比率から決定的に操作列を生成する設定
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Workload {
+    /// 生成する操作数（prefill分は含まない）
+    pub ops: usize,
+    pub seed: u64,
+    pub mix: OpMix,
+    pub dist: PositionDist,
+    /// 本番の操作の前に末尾へ追加しておく要素数
+    pub prefill: usize,
+}
+
+impl Workload {
+    pub fn new(ops: usize, seed: u64, mix: OpMix, dist: PositionDist) -> Self {
+        Self { ops, seed, mix, dist, prefill: 0 }
+    }
+
+    /// 旧 `run_*_insert_py` と同じ操作列（seed=123456789, `seed % len` の位置に i を挿入）
+    pub fn random_insert(ops: usize) -> Self {
+        Self::new(ops, 123456789, OpMix::INSERT, PositionDist::Uniform)
+    }
+
+    /// 0..ops を末尾に追加するだけの操作列
+    pub fn append_only(ops: usize) -> Self {
+        Self::new(ops, 0, OpMix::APPEND, PositionDist::Uniform)
+    }
+
+    /// Python/JS/CLI から文字列で指定された操作比率・位置分布を解釈する
+    pub fn from_spec(ops: usize, seed: u64, mix: &str, dist: &str) -> Result<Self, String> {
+        Ok(Self::new(ops, seed, mix.parse()?, dist.parse()?))
+    }
+
+    pub fn with_prefill(mut self, prefill: usize) -> Self {
+        self.prefill = prefill;
+        self
+    }
+
+    /// 操作列をその場で生成するイテレータ
+    pub fn stream(&self) -> OpStream {
+        OpStream {
+            workload: *self,
+            state: self.seed & 0x7fffffff,
+            single: self.mix.single(),
+            emitted: 0,
+            len: 0,
+            cursor: 0,
+        }
+    }
+
+    /// 計測から生成コストを外したい場合は事前にVecへ展開しておく
+    pub fn generate(&self) -> Vec<Op> {
+        self.stream().collect()
+    }
+
+    /// 操作列を list に流し込み、結果のチェックサム（wrapping加算）を返す
+    pub fn run<B: SequenceBackend>(&self, list: &mut B) -> i64 {
+        run_ops(list, self.stream())
+    }
+
+    pub fn try_run<B: SequenceBackend>(&self, list: &mut B) -> Result<i64, AllocError> {
+        try_run_ops(list, self.stream())
+    }
+}
+
+/// 任意の操作列を list に流し込み、結果のチェックサム（wrapping加算）を返す
+pub fn run_ops<B: DynSequence + ?Sized, I: IntoIterator<Item = Op>>(list: &mut B, ops: I) -> i64 {
+    let mut checksum: i64 = 0;
+    for op in ops {
+        if let Some(v) = op.apply(list) {
+            checksum = checksum.wrapping_add(v);
+        }
+    }
+    checksum
+}
+
+/// `run_ops` の確保失敗を返す版。失敗した時点で止める。
+pub fn try_run_ops<B: DynSequence + ?Sized, I: IntoIterator<Item = Op>>(list: &mut B, ops: I) -> Result<i64, AllocError> {
+    let mut checksum: i64 = 0;
+    for op in ops {
+        if let Some(v) = op.try_apply(list)? {
+            checksum = checksum.wrapping_add(v);
+        }
+    }
+    Ok(checksum)
+}
+
+/// `Workload` から操作を1つずつ生成する。
+/// リスト長を追跡しているので、空のリストへのRemove/GetはAppendに置き換える。
+pub struct OpStream {
+    workload: Workload,
+    state: u64,
+    single: Option<OpKind>,
+    emitted: usize,
+    len: usize,
+    cursor: usize,
+}
+
+impl OpStream {
+    // 旧ループと同じ線形合同法: 現在値を返してから進める
+    fn next_raw(&mut self) -> u64 {
+        let out = self.state;
+        self.state = (self.state * 1103515245 + 12345) & 0x7fffffff;
+        out
+    }
+
+    // [0, 1) の一様乱数
+    fn next_unit(&mut self) -> f64 {
+        self.next_raw() as f64 / (1u64 << 31) as f64
+    }
+
+    fn position(&mut self, len: usize) -> usize {
+        if len == 0 {
+            // 旧ループと同様、空でも乱数は1つ消費する
+            self.next_raw();
+            return 0;
+        }
+        let pos = match self.workload.dist {
+            PositionDist::Uniform => (self.next_raw() % len as u64) as usize,
+            PositionDist::FrontBiased => {
+                let u = self.next_unit();
+                (len as f64 * u * u) as usize
+            }
+            PositionDist::BackBiased => {
+                let u = self.next_unit();
+                len - 1 - ((len as f64 * u * u) as usize).min(len - 1)
+            }
+            PositionDist::Zipf(s) => {
+                let u = self.next_unit();
+                let n = len as f64 + 1.0;
+                let x = if (s - 1.0).abs() < 1e-9 {
+                    n.powf(u)
+                } else {
+                    let a = 1.0 - s;
+                    ((n.powf(a) - 1.0) * u + 1.0).powf(1.0 / a)
+                };
+                (x - 1.0) as usize
+            }
+            PositionDist::Sequential(max_step) => {
+                // usize::MAX のような大きな幅でも溢れないよう i128 で計算する（小さい幅では同じ位置列）
+                let max_step = max_step as i128;
+                let step = (self.next_raw() as i128) % (2 * max_step + 1) - max_step;
+                self.cursor = (self.cursor as i128 + step).clamp(0, len as i128 - 1) as usize;
+                self.cursor
+            }
+            PositionDist::Head => 0,
+            PositionDist::Tail => len - 1,
+        };
+        pos.min(len - 1)
+    }
+}
+
+impl Iterator for OpStream {
+    type Item = Op;
+
+    fn next(&mut self) -> Option<Op> {
+        let total = self.workload.prefill + self.workload.ops;
+        if self.emitted >= total {
+            return None;
+        }
+        let i = self.emitted;
+        self.emitted += 1;
+        let value = i as i32;
+
+        if i < self.workload.prefill {
+            self.len += 1;
+            return Some(Op::Append(value));
+        }
+
+        let mut kind = match self.single {
+            Some(kind) => kind,
+            None => {
+                let r = self.next_raw();
+                self.workload.mix.pick(r)
+            }
+        };
+        if self.len == 0 && matches!(kind, OpKind::Remove | OpKind::Get) {
+            kind = OpKind::Append;
+        }
+
+        let op = match kind {
+            OpKind::Append => {
+                self.len += 1;
+                Op::Append(value)
+            }
+            OpKind::Insert => {
+                let pos = self.position(self.len);
+                self.len += 1;
+                if matches!(self.workload.dist, PositionDist::Sequential(_)) {
+                    // タイピングと同じく、挿入した文字の後ろへカーソルを進める
+                    self.cursor = pos + 1;
+                }
+                Op::Insert(pos, value)
+            }
+            OpKind::Remove => {
+                let pos = self.position(self.len);
+                self.len -= 1;
+                Op::Remove(pos)
+            }
+            OpKind::Get => Op::Get(self.position(self.len)),
+            OpKind::Sum => Op::Sum,
+        };
+        Some(op)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.workload.prefill + self.workload.ops - self.emitted;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for OpStream {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISTS: [PositionDist; 8] = [
+        PositionDist::Uniform,
+        PositionDist::FrontBiased,
+        PositionDist::BackBiased,
+        PositionDist::Zipf(1.0),
+        PositionDist::Zipf(1.5),
+        PositionDist::Sequential(8),
+        PositionDist::Head,
+        PositionDist::Tail,
+    ];
+
+    #[test]
+    fn same_seed_mix_and_dist_give_the_same_stream() {
+        for dist in DISTS {
+            let workload = Workload::new(2_000, 42, OpMix::EDITOR, dist).with_prefill(10);
+            assert_eq!(workload.generate(), workload.generate(), "{}", dist);
+            // 別のシードなら（ほぼ確実に）別の列になる
+            let other = Workload { seed: 43, ..workload };
+            assert_ne!(workload.generate(), other.generate(), "{}", dist);
+        }
+    }
+
+    #[test]
+    fn remove_and_get_on_an_empty_list_become_append() {
+        // 削除と参照しかない比率でも、空のときは Append になる
+        let mix = OpMix { append: 0, insert: 0, remove: 1, get: 1, sum: 0 };
+        let ops = Workload::new(100, 7, mix, PositionDist::Uniform).generate();
+        assert!(matches!(ops[0], Op::Append(0)));
+        let mut len = 0usize;
+        for op in ops {
+            match op {
+                Op::Append(_) => {
+                    assert_eq!(len, 0, "Append is only generated for an empty list");
+                    len += 1;
+                }
+                Op::Remove(_) => len -= 1,
+                Op::Get(_) => assert!(len > 0),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+
+    // 位置指定の操作が、その時点の長さの範囲に収まっていること
+    fn assert_in_bounds(workload: &Workload) {
+        let label = format!("{} {}", workload.mix, workload.dist);
+        let mut len = 0usize;
+        for op in workload.stream() {
+            match op {
+                Op::Append(_) => len += 1,
+                Op::Insert(pos, _) => {
+                    assert!(pos <= len, "{}: insert at {} (len {})", label, pos, len);
+                    len += 1;
+                }
+                Op::Remove(pos) => {
+                    assert!(pos < len, "{}: remove at {} (len {})", label, pos, len);
+                    len -= 1;
+                }
+                Op::Get(pos) => assert!(pos < len, "{}: get at {} (len {})", label, pos, len),
+                Op::Sum | Op::Clear => {}
+            }
+        }
+    }
+
+    #[test]
+    fn every_position_dist_stays_in_bounds() {
+        let mixes = [OpMix::INSERT, OpMix::EDITOR, OpMix::QUEUE, OpMix { append: 1, insert: 1, remove: 2, get: 1, sum: 0 }];
+        for dist in DISTS {
+            for mix in mixes {
+                assert_in_bounds(&Workload::new(5_000, 123456789, mix, dist).with_prefill(3));
+            }
+        }
+    }
+
+    #[test]
+    fn huge_sequential_steps_do_not_overflow() {
+        let wide: PositionDist = format!("sequential:{}", usize::MAX).parse().unwrap();
+        assert_eq!(wide, PositionDist::Sequential(usize::MAX));
+        for dist in [wide, PositionDist::Sequential(u32::MAX as usize + 1), PositionDist::Sequential(i64::MAX as usize)] {
+            assert_in_bounds(&Workload::new(2_000, 5, OpMix::EDITOR, dist).with_prefill(4));
+        }
+    }
+
+    #[test]
+    fn stream_matches_a_real_list() {
+        // 生成した操作を Vec に流しても範囲外が起きず、run_ops は全部の結果を足している
+        let workload = Workload::new(3_000, 9, OpMix::EDITOR, PositionDist::Zipf(1.2));
+        let mut list = crate::ZipperList::new();
+        let mut model: Vec<i32> = Vec::new();
+        let mut expected: i64 = 0;
+        for op in workload.stream() {
+            match op {
+                Op::Append(v) => model.push(v),
+                Op::Insert(pos, v) => model.insert(pos, v),
+                Op::Remove(pos) => expected = expected.wrapping_add(model.remove(pos) as i64),
+                Op::Get(pos) => expected = expected.wrapping_add(model[pos] as i64),
+                Op::Sum => expected = expected.wrapping_add(model.iter().map(|&v| v as i64).sum::<i64>()),
+                Op::Clear => model.clear(),
+            }
+        }
+        assert_eq!(workload.run(&mut list), expected);
+        assert_eq!(list.to_vec(), model);
+    }
+}
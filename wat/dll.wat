@@ -101,14 +101,15 @@
 
   ;; ==========================================
   ;; Sum (合計計算・トラバーサル)
+  ;; i32で足すと N=100,000 で溢れるので、i64に符号拡張して足す
   ;; ==========================================
-  (func $sum (result i32)
+  (func $sum (result i64)
     (local $current i32)
-    (local $acc i32)
+    (local $acc i64)
     
     global.get $ADDR_HEAD
     local.set $current
-    i32.const 0
+    i64.const 0
     local.set $acc
 
     (block $break
@@ -117,13 +118,14 @@
         i32.eqz
         br_if $break
 
-        ;; acc += current.val
+        ;; acc += (i64)current.val
         local.get $acc
         local.get $current
         global.get $OFF_VAL
         i32.add
         i32.load
-        i32.add
+        i64.extend_i32_s
+        i64.add
         local.set $acc
 
         ;; current = current.next
@@ -139,6 +141,82 @@
     local.get $acc
   )
 
+  ;; ==========================================
+  ;; Checksum (Rust版の Checksum と同じ値。直前の run_wat_dll のリストを読む)
+  ;; ==========================================
+  (func $checksum_len (export "checksum_len") (result i32)
+    (local $current i32)
+    (local $len i32)
+
+    global.get $ADDR_HEAD
+    local.set $current
+
+    (block $break
+      (loop $top
+        local.get $current
+        i32.eqz
+        br_if $break
+
+        local.get $len
+        i32.const 1
+        i32.add
+        local.set $len
+
+        local.get $current
+        global.get $OFF_NEXT
+        i32.add
+        i32.load
+        local.set $current
+
+        br $top
+      )
+    )
+    local.get $len
+  )
+
+  (func $checksum_sum (export "checksum_sum") (result i64)
+    call $sum
+  )
+
+  ;; h = h * 0x01000193 + value (mod 2^32)。順序が変わると値が変わる
+  (func $checksum_hash (export "checksum_hash") (result i32)
+    (local $current i32)
+    (local $hash i32)
+
+    global.get $ADDR_HEAD
+    local.set $current
+    i32.const 0x811c9dc5
+    local.set $hash
+
+    (block $break
+      (loop $top
+        local.get $current
+        i32.eqz
+        br_if $break
+
+        ;; hash = hash * PRIME + current.val
+        local.get $hash
+        i32.const 0x01000193
+        i32.mul
+        local.get $current
+        global.get $OFF_VAL
+        i32.add
+        i32.load
+        i32.add
+        local.set $hash
+
+        local.get $current
+        global.get $OFF_NEXT
+        i32.add
+        i32.load
+        local.set $current
+
+        br $top
+      )
+    )
+    local.get $hash
+  )
+
   ;; ==========================================
   ;; 【重要】メイン実行関数 (JSから呼ばれる)
   ;; ==========================================
@@ -167,7 +245,8 @@
       )
     )
     
-    ;; 3. 合計を計算して返す
+    ;; 3. 合計を計算して返す (戻り値は i32 のままなので下位32bit。全体は checksum_sum)
     call $sum
+    i32.wrap_i64
   )
 )
\ No newline at end of file
@@ -1,4 +1,4 @@
-import initRust, { run_rust_dll, run_rust_unsafe, run_rust_bump, run_wgpu_dll } from './pkg/polyglot_compute_lab.js';
+import initRust, { run_rust_dll, run_rust_unsafe, run_rust_bump, run_rust_soa_bump, run_rust_gap_buffer, run_rust_unrolled, run_wgpu_dll_checksum, checksum_rust } from './pkg/polyglot_compute_lab.js';
 
 const ITERATIONS = 100000;
 
@@ -7,6 +7,25 @@ function log(msg) {
     console.log(msg);
 }
 
+// Rust側で計算した基準値（Zig/WAT/WGPUの結果と突き合わせる）
+let reference = null;
+
+// { len, sum, hash } を表示用に整形し、基準値と一致するかを付ける
+function checksumText(c) {
+    const hash = c.hash >>> 0;
+    const ok = reference !== null
+        && Number(c.len) === reference.len
+        && BigInt(c.sum) === BigInt(reference.sum)
+        && hash === reference.hash;
+    return `Sum: ${c.sum}, Hash: 0x${hash.toString(16).padStart(8, '0')} ${ok ? '✅' : '❌'}`;
+}
+
+// Zig/WATモジュールの checksum_* エクスポートを読む（再ビルド前の古い .wasm には無い）
+function wasmChecksum(exports, sum) {
+    if (!exports.checksum_sum) return `Sum: ${sum}`;
+    return checksumText({ len: exports.checksum_len(), sum: exports.checksum_sum(), hash: exports.checksum_hash() });
+}
+
 async function loadWasm(path) {
     const response = await fetch(path);
     const bytes = await response.arrayBuffer();
@@ -26,6 +45,9 @@ async function main() {
     // 3. Load WAT
     const watExports = await loadWasm('./wat_dll.wasm');
 
+    reference = checksum_rust("safe", ITERATIONS, 0, "append", "uniform");
+    log(`Reference (Rust): len=${reference.len} sum=${reference.sum} hash=0x${reference.hash.toString(16)}`);
+
     log("All modules loaded. Ready to benchmark.");
 
     // Setup Handlers
@@ -58,18 +80,51 @@ async function main() {
         }, 10);
     };
 
+    // Rust (SoA-Bump)
+    document.getElementById('btn-rust-soa-bump').onclick = () => {
+        log("Running Rust (SoA-Bump)...");
+        setTimeout(() => {
+            const start = performance.now();
+            const sum = run_rust_soa_bump(ITERATIONS);
+            const time = performance.now() - start;
+            document.getElementById('res-rust-soa-bump').innerText = `${time.toFixed(2)} ms (Sum: ${sum})`;
+        }, 10);
+    };
+
+    // Rust (Gap Buffer)
+    document.getElementById('btn-rust-gap').onclick = () => {
+        log("Running Rust (Gap Buffer)...");
+        setTimeout(() => {
+            const start = performance.now();
+            const sum = run_rust_gap_buffer(ITERATIONS);
+            const time = performance.now() - start;
+            document.getElementById('res-rust-gap').innerText = `${time.toFixed(2)} ms (Sum: ${sum})`;
+        }, 10);
+    };
+
+    // Rust (Unrolled Linked List)
+    document.getElementById('btn-rust-unrolled').onclick = () => {
+        log("Running Rust (Unrolled)...");
+        setTimeout(() => {
+            const start = performance.now();
+            const sum = run_rust_unrolled(ITERATIONS, 256);
+            const time = performance.now() - start;
+            document.getElementById('res-rust-unrolled').innerText = `${time.toFixed(2)} ms (Sum: ${sum})`;
+        }, 10);
+    };
+
     document.getElementById('btn-zig').onclick = () => {
         const start = performance.now();
         const sum = zigExports.run_zig_dll(ITERATIONS);
         const time = performance.now() - start;
-        document.getElementById('res-zig').innerText = `${time.toFixed(2)} ms (Sum: ${sum})`;
+        document.getElementById('res-zig').innerText = `${time.toFixed(2)} ms (${wasmChecksum(zigExports, sum)})`;
     };
 
     document.getElementById('btn-wat').onclick = () => {
         const start = performance.now();
         const sum = watExports.run_wat_dll(ITERATIONS);
         const time = performance.now() - start;
-        document.getElementById('res-wat').innerText = `${time.toFixed(2)} ms (Sum: ${sum})`;
+        document.getElementById('res-wat').innerText = `${time.toFixed(2)} ms (${wasmChecksum(watExports, sum)})`;
     };
 
     // WGPU
@@ -87,9 +142,9 @@ async function main() {
         try {
             const start = performance.now();
             // Rustのasync関数をJSのawaitで待つ
-            const sum = await run_wgpu_dll(ITERATIONS);
+            const checksum = await run_wgpu_dll_checksum(ITERATIONS);
             const time = performance.now() - start;
-            document.getElementById('res-wgpu').innerText = `${time.toFixed(2)} ms (Sum: ${sum})`;
+            document.getElementById('res-wgpu').innerText = `${time.toFixed(2)} ms (${checksumText(checksum)})`;
         } catch (e) {
             log(`❌ WGPU Error: ${e}`);
             document.getElementById('res-wgpu').innerText = "Error";
@@ -8,6 +8,19 @@ var heap_buffer: [64 * 1024 * 1024]u8 = undefined;
 var fba = std.heap.FixedBufferAllocator.init(&heap_buffer);
 const allocator = fba.allocator();
 
+// Rust版の Checksum と同じ値: 要素数 / i64の合計 / h = h * PRIME + value (mod 2^32)
+const Checksum = struct {
+    len: u64 = 0,
+    sum: i64 = 0,
+    hash: u32 = 0x811c9dc5,
+
+    fn push(self: *Checksum, value: i32) void {
+        self.len += 1;
+        self.sum +%= value;
+        self.hash = self.hash *% 0x01000193 +% @as(u32, @bitCast(value));
+    }
+};
+
 const Node = struct {
     value: i32,
     prev: ?*Node,
@@ -41,8 +54,9 @@ const DoublyLinkedList = struct {
         }
     }
 
-    pub fn sum(self: *DoublyLinkedList) i32 {
-        var s: i32 = 0;
+    // i32で足すと N=100,000 で溢れるので i64 で足す
+    pub fn sum(self: *const DoublyLinkedList) i64 {
+        var s: i64 = 0;
         var current = self.head;
         while (current) |node| {
             s += node.value;
@@ -50,14 +64,27 @@ const DoublyLinkedList = struct {
         }
         return s;
     }
+
+    pub fn checksum(self: *const DoublyLinkedList) Checksum {
+        var c = Checksum{};
+        var current = self.head;
+        while (current) |node| {
+            c.push(node.value);
+            current = node.next;
+        }
+        return c;
+    }
 };
 
+// チェックサム用に直前の実行のリストを残しておく（ノードは次の fba.reset() まで有効）
+var dll = DoublyLinkedList.init();
+
 // WASMから呼び出すためのエクスポート関数
 export fn run_zig_dll(iterations: i32) i32 {
     // ベンチマークごとにアロケータをリセット（メモリを再利用）
     fba.reset();
     
-    var dll = DoublyLinkedList.init();
+    dll = DoublyLinkedList.init();
     
     var i: i32 = 0;
     while (i < iterations) : (i += 1) {
@@ -66,5 +93,18 @@ export fn run_zig_dll(iterations: i32) i32 {
         dll.append(i) catch return -1;
     }
     
+    // 戻り値は i32 のままなので下位32bit（全体は checksum_sum）
+    return @truncate(dll.sum());
+}
+
+export fn checksum_len() i32 {
+    return @intCast(dll.checksum().len);
+}
+
+export fn checksum_sum() i64 {
     return dll.sum();
+}
+
+export fn checksum_hash() u32 {
+    return dll.checksum().hash;
 }
\ No newline at end of file
@@ -4,6 +4,19 @@ var heap_buffer: [64 * 1024 * 1024]u8 = undefined;
 var fba = std.heap.FixedBufferAllocator.init(&heap_buffer);
 const allocator = fba.allocator();
 
+// Rust版の Checksum と同じ値: 要素数 / i64の合計 / h = h * PRIME + value (mod 2^32)
+const Checksum = struct {
+    len: u64 = 0,
+    sum: i64 = 0,
+    hash: u32 = 0x811c9dc5,
+
+    fn push(self: *Checksum, value: i32) void {
+        self.len += 1;
+        self.sum +%= value;
+        self.hash = self.hash *% 0x01000193 +% @as(u32, @bitCast(value));
+    }
+};
+
 const ZipperList = struct {
     left: std.ArrayList(i32),
     right: std.ArrayList(i32),
@@ -20,6 +33,15 @@ const ZipperList = struct {
         self.right.deinit(allocator);
     }
 
+    // 先頭から順に: 左スタックは前から、右スタックは後ろから
+    pub fn checksum(self: *const ZipperList) Checksum {
+        var c = Checksum{};
+        for (self.left.items) |v| c.push(v);
+        var k: usize = self.right.items.len;
+        while (k > 0) : (k -= 1) c.push(self.right.items[k - 1]);
+        return c;
+    }
+
     pub fn insert(self: *ZipperList, index: usize, value: i32) !void {
         const current_pos = self.left.items.len;
 
@@ -72,10 +94,12 @@ const ZipperList = struct {
     }
 };
 
+// チェックサム用に直前の実行のリストを残しておく（deinitせず、次の fba.reset() でまとめて解放）
+var zipper = ZipperList.init();
+
 export fn run_zig_zipper(iterations: i32) i32 {
     fba.reset();
-    var zipper = ZipperList.init();
-    defer zipper.deinit();
+    zipper = ZipperList.init();
 
     var seed: usize = 123456789;
     var len: usize = 0;
@@ -89,4 +113,16 @@ export fn run_zig_zipper(iterations: i32) i32 {
     }
 
     return 0;
-}
\ No newline at end of file
+}
+
+export fn checksum_len() i32 {
+    return @intCast(zipper.checksum().len);
+}
+
+export fn checksum_sum() i64 {
+    return zipper.checksum().sum;
+}
+
+export fn checksum_hash() u32 {
+    return zipper.checksum().hash;
+}